@@ -3,40 +3,80 @@ pub mod models;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use std::error::Error;
+use std::fs::File;
+use std::io;
 
-use models::config::Config;
+use models::config::{Config, USAGE};
 
-use crate::models::ant_colony_optimization::aco::ACO;
-use crate::models::ant_colony_optimization::aco_parameters;
-use crate::models::ant_colony_optimization::edge_ac::EdgeAC;
-use crate::models::ant_colony_optimization::vertex_ac::VertexAC;
-use crate::models::graph::rejectability::create_rejectability_graph;
+use crate::models::classifier::model_file::ModelFile;
+use crate::models::classifier::multi_class::train_multi_class_model;
+use crate::models::classifier::prediction::predict_csv;
+use crate::models::classifier::rule_model::RuleForm;
+use crate::models::classifier::training::train_model;
+use crate::models::evaluation::confusion_matrix::ConfusionMatrix;
+use crate::models::evaluation::cross_validation::cross_validate;
+use crate::models::evaluation::multi_class_confusion_matrix::MultiClassConfusionMatrix;
 
 #[macro_use(c)]
 extern crate cute;
 
-const SEED: u64 = 1000;
-
 pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
     match config {
-        Config::Help() => println!("Help"),
-        Config::Run(dataset, class_column, positive_class, learning_frac, algorithm) => {
+        Config::Help() => println!("{}", USAGE),
+        Config::Run(run_config) => {
+            let models::config::RunConfig {
+                dataset,
+                class_column,
+                positive_class,
+                learning_frac,
+                split,
+                testing_dataset,
+                save_model,
+                save_dnf_model,
+                seed,
+                training,
+                algorithm,
+            } = *run_config;
+
             println!("Run");
             println!("Dataset: {}", dataset);
             println!("Class column: {}", class_column);
             println!("Positive class: {}", positive_class);
-            println!("Learning fraction: {}", learning_frac);
+            match &testing_dataset {
+                Some(testing_dataset) => println!("Testing dataset: {}", testing_dataset),
+                None => {
+                    println!("Learning fraction: {}", learning_frac);
+                    println!("Split: {:?}", split);
+                }
+            }
+            println!("Seed: {}", seed);
+            println!("Threads: {}", training.threads);
+            println!("Prune: {}", training.prune);
+            println!(
+                "Noise tolerance: {} of the positives, {} of the negatives",
+                training.tolerance.positives, training.tolerance.negatives
+            );
+            println!("Forms: {:?}", training.forms);
             println!("Algorithm: {:?}", algorithm);
 
-            let rng = StdRng::seed_from_u64(SEED);
+            let rng = StdRng::seed_from_u64(seed);
 
-            let dataset = models::data_handling::dataset::Dataset::new(
-                rng.clone(),
-                &dataset,
-                &class_column,
-                &positive_class,
-                learning_frac,
-            );
+            let dataset = match &testing_dataset {
+                Some(testing_dataset) => models::data_handling::dataset::Dataset::from_files(
+                    &dataset,
+                    testing_dataset,
+                    &class_column,
+                    &positive_class,
+                )?,
+                None => models::data_handling::dataset::Dataset::with_split(
+                    rng.clone(),
+                    &dataset,
+                    &class_column,
+                    &positive_class,
+                    learning_frac,
+                    split,
+                )?,
+            };
 
             println!("Learning positive len: {}", dataset.learning_pos.len());
             println!("Learning negative len: {}", dataset.learning_neg.len());
@@ -44,49 +84,168 @@ pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
             println!("Testing negative len: {}", dataset.testing_neg.len());
             println!("Dataset: {}", dataset);
 
-            let graph = create_rejectability_graph(rng.clone(), &dataset);
+            for form in training.forms {
+                let model = train_model(rng.clone(), &dataset, &algorithm, &training, *form);
+
+                println!("{} model: |{}| {}", form, model.len(), model);
+
+                let evaluation = ConfusionMatrix::from_testing_set(&model, &dataset);
+                println!("Testing set evaluation");
+                println!("{}", evaluation);
+
+                let path = match form {
+                    RuleForm::Cnf => &save_model,
+                    RuleForm::Dnf => &save_dnf_model,
+                };
+                if let Some(path) = path {
+                    let model_file = ModelFile {
+                        model,
+                        schema: dataset.schema(),
+                        class_column: class_column.clone(),
+                        positive_class: positive_class.clone(),
+                        parameters: [algorithm.parameters(), training.parameters()].concat(),
+                        seed,
+                    };
+                    model_file.save(path)?;
+                    println!("{} model saved to {}", form, path);
+                }
+            }
+        }
+        Config::CrossValidate(cv_config) => {
+            let models::config::CrossValidationConfig {
+                dataset,
+                class_column,
+                positive_class,
+                folds,
+                seed,
+                training,
+                algorithm,
+            } = *cv_config;
 
+            println!("Cross-validation");
+            println!("Dataset: {}", dataset);
+            println!("Class column: {}", class_column);
+            println!("Positive class: {}", positive_class);
+            println!("Folds: {}", folds);
+            println!("Seed: {}", seed);
+            println!("Threads: {}", training.threads);
+            println!("Prune: {}", training.prune);
             println!(
-                "Rejectability graph created. Number of nodes: {}",
-                graph.n_vertex
+                "Noise tolerance: {} of the positives, {} of the negatives",
+                training.tolerance.positives, training.tolerance.negatives
             );
+            println!("Forms: {:?}", training.forms);
+            println!("Algorithm: {:?}", algorithm);
 
-            match *algorithm {
-                models::config::Algorithm::Ants(aco_algo, mut aco_parameters) => {
-                    aco_parameters.graph = graph;
-                    aco_parameters.rand = rng;
-                    match aco_algo {
-                        aco_parameters::ACOAlgorithm::VertexAC => {
-                            let mut vertex_ac = VertexAC::new(&aco_parameters);
-                            while !aco_parameters.graph.available_vertex.is_empty() {
-                                let best_clique = vertex_ac.aco_procedure(&mut aco_parameters);
-                                aco_parameters
-                                    .graph
-                                    .remove_vertex_set_from_available(&best_clique);
-                                println!("Best clique: |{}| {:?}", best_clique.len(), best_clique);
-                                println!(
-                                    "Clique clause: {}",
-                                    &aco_parameters.graph.get_clique_clause(best_clique)
-                                );
-                            }
-                        }
-                        aco_parameters::ACOAlgorithm::EdgeAC => {
-                            let mut edge_ac = EdgeAC::new(&aco_parameters);
-                            while !aco_parameters.graph.available_vertex.is_empty() {
-                                let best_clique = edge_ac.aco_procedure(&mut aco_parameters);
-                                aco_parameters
-                                    .graph
-                                    .remove_vertex_set_from_available(&best_clique);
-                                println!("Best clique: |{}| {:?}", best_clique.len(), best_clique);
-                                println!(
-                                    "Clique clause: {}",
-                                    &aco_parameters.graph.get_clique_clause(best_clique)
-                                );
-                            }
-                        }
-                    };
+            let rng = StdRng::seed_from_u64(seed);
+
+            let fold_datasets = models::data_handling::dataset::Dataset::k_folds(
+                rng.clone(),
+                &dataset,
+                &class_column,
+                &positive_class,
+                folds,
+            )?;
+
+            for (form, report) in cross_validate(rng, &fold_datasets, &algorithm, &training) {
+                println!("Cross-validation results of the {} model", form);
+                println!("{}", report);
+            }
+        }
+        Config::MultiClass(multi_class_config) => {
+            let models::config::MultiClassConfig {
+                dataset,
+                class_column,
+                strategy,
+                learning_frac,
+                split,
+                seed,
+                training,
+                algorithm,
+            } = *multi_class_config;
+
+            println!("Multi-class");
+            println!("Dataset: {}", dataset);
+            println!("Class column: {}", class_column);
+            println!("Strategy: {:?}", strategy);
+            println!("Learning fraction: {}", learning_frac);
+            println!("Split: {:?}", split);
+            println!("Seed: {}", seed);
+            println!("Threads: {}", training.threads);
+            println!("Prune: {}", training.prune);
+            println!(
+                "Noise tolerance: {} of the positives, {} of the negatives",
+                training.tolerance.positives, training.tolerance.negatives
+            );
+            println!("Forms: {:?}", training.forms);
+            println!("Algorithm: {:?}", algorithm);
+
+            let rng = StdRng::seed_from_u64(seed);
+
+            let dataset =
+                models::data_handling::multi_class_dataset::MultiClassDataset::with_split(
+                    rng.clone(),
+                    &dataset,
+                    &class_column,
+                    learning_frac,
+                    split,
+                )?;
+
+            println!("Classes: {}", dataset.classes.join(", "));
+            println!("Learning len: {}", dataset.learning.len());
+            println!("Testing len: {}", dataset.testing.len());
+
+            for form in training.forms {
+                let model = train_multi_class_model(
+                    rng.clone(),
+                    &dataset,
+                    strategy,
+                    &algorithm,
+                    &training,
+                    *form,
+                );
+
+                println!("{} models:", form);
+                println!("{}", model);
+
+                let evaluation = MultiClassConfusionMatrix::from_testing_set(&model, &dataset);
+                println!("Testing set evaluation");
+                println!("{}", evaluation);
+            }
+        }
+        Config::Predict(predict_config) => {
+            let model_file = ModelFile::load(&predict_config.model)?;
+
+            match &predict_config.output {
+                Some(output) => {
+                    let file = File::create(output)?;
+                    let summary = predict_csv(
+                        &model_file,
+                        &predict_config.input,
+                        file,
+                        &predict_config.predicted_column,
+                        &predict_config.negative_label,
+                    )?;
+                    println!(
+                        "Predicted {} rows: {} {}, {} {}. Written to {}",
+                        summary.positive + summary.negative,
+                        summary.positive,
+                        model_file.positive_class,
+                        summary.negative,
+                        predict_config.negative_label,
+                        output
+                    );
                 }
-            };
+                None => {
+                    predict_csv(
+                        &model_file,
+                        &predict_config.input,
+                        io::stdout(),
+                        &predict_config.predicted_column,
+                        &predict_config.negative_label,
+                    )?;
+                }
+            }
         }
     }
 
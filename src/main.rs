@@ -7,6 +7,7 @@ fn main() {
 
     let config = Config::new(&args).unwrap_or_else(|err| {
         println!("There's been a problem reading the arguments: {}", err);
+        println!("Run `oqat help` to see the available options");
         process::exit(1);
     });
 
@@ -1,5 +1,8 @@
 pub mod ant_colony_optimization;
+pub mod classifier;
 pub mod clause;
+pub mod clique_solvers;
 pub mod config;
 pub mod data_handling;
+pub mod evaluation;
 pub mod graph;
@@ -1,10 +1,38 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, fmt::Display, mem, thread, time::Instant};
 
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
-use super::aco_parameters::ACOParameters;
+use crate::models::{
+    classifier::cnf_model::CnfModel,
+    clique_solvers::clique_cover,
+    graph::{incremental_clique::IncrementalClique, rejectability_graph::Graph},
+};
 
-pub trait ACO {
+use super::aco_parameters::{ACOHeuristic, ACOParameters, PheromoneUpdate};
+
+// why a clique search stopped
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopCriterion {
+    MaxCycles,
+    NoImprovement,
+    TimeBudget,
+    // the best clique reached the size bound of the available vertices
+    UpperBound,
+}
+
+impl Display for StopCriterion {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let reason = match self {
+            StopCriterion::MaxCycles => "maximum number of cycles",
+            StopCriterion::NoImprovement => "cycles without improvement",
+            StopCriterion::TimeBudget => "time budget",
+            StopCriterion::UpperBound => "clique size upper bound",
+        };
+        write!(f, "{}", reason)
+    }
+}
+
+pub trait ACO: Sync {
     fn set_initial_pheromone_trails(&mut self, p: &ACOParameters);
 
     fn tau_factor_of_vertex(&self, vertex: &usize, current_clique: &HashSet<usize>) -> f64;
@@ -23,151 +51,233 @@ pub trait ACO {
         p: &ACOParameters,
         global_best_clique: &HashSet<usize>,
         k_best_clique: &HashSet<usize>,
+        ant_cliques: &[HashSet<usize>],
     ) {
         self.decrement_pheromone(p);
-        let pheromone_delta =
-            1.0 / (1.0 + global_best_clique.len() as f64 - k_best_clique.len() as f64);
-        self.increment_pheromone(p, &pheromone_delta, k_best_clique);
+        let pheromone_delta = |clique: &HashSet<usize>| {
+            1.0 / (1.0 + global_best_clique.len() as f64 - clique.len() as f64)
+        };
+
+        match p.pheromone_update {
+            PheromoneUpdate::IterationBest | PheromoneUpdate::MaxMin(_) => {
+                self.increment_pheromone(p, &pheromone_delta(k_best_clique), k_best_clique);
+            }
+            PheromoneUpdate::GlobalBest => {
+                self.increment_pheromone(p, &1.0, global_best_clique);
+            }
+            PheromoneUpdate::Elitist => {
+                self.increment_pheromone(p, &pheromone_delta(k_best_clique), k_best_clique);
+                self.increment_pheromone(p, &1.0, global_best_clique);
+            }
+            PheromoneUpdate::RankBased(w) => {
+                // largest cliques first, ties keep the order of the ants
+                let mut ranked = ant_cliques.iter().collect::<Vec<&HashSet<usize>>>();
+                ranked.sort_by_key(|clique| std::cmp::Reverse(clique.len()));
+                for (rank, clique) in ranked.iter().take(w.saturating_sub(1)).enumerate() {
+                    let weight = (w - 1 - rank) as f64 / w as f64;
+                    self.increment_pheromone(p, &(weight * pheromone_delta(clique)), clique);
+                }
+                self.increment_pheromone(p, &1.0, global_best_clique);
+            }
+        }
     }
 
-    fn vertex_probability(
+    fn eta_factor_of_vertex(
         &self,
         p: &ACOParameters,
         vertex: &usize,
-        current_clique: &HashSet<usize>,
-        memory_tau: &mut Vec<f64>,
-        memory_sum_tau_candidates: &f64,
+        candidates: &HashSet<usize>,
+        current_clique: &IncrementalClique,
     ) -> f64 {
-        let tau: f64 = if memory_tau[*vertex] > 0.0 {
-            memory_tau[*vertex]
-        } else {
-            self.tau_factor_of_vertex(vertex, current_clique)
-        };
+        match p.heuristic {
+            ACOHeuristic::Degree => {
+                let degree = candidates
+                    .iter()
+                    .filter(|other_v| p.graph.is_edge(*vertex, **other_v))
+                    .count();
+                1.0 + degree as f64
+            }
+            ACOHeuristic::ClauseValues => {
+                1.0 + current_clique.clause_with(&p.graph, *vertex).n_values() as f64
+            }
+        }
+    }
+
+    // unnormalized transition weight tau^alpha * eta^beta of a candidate
+    fn vertex_weight(
+        &self,
+        p: &ACOParameters,
+        vertex: &usize,
+        candidates: &HashSet<usize>,
+        current_clique: &IncrementalClique,
+    ) -> f64 {
+        let tau = self
+            .tau_factor_of_vertex(vertex, &current_clique.clique)
+            .powf(p.alpha);
+        if p.beta == 0.0 {
+            return tau;
+        }
 
-        let tau = tau.powf(p.alpha);
-        tau / memory_sum_tau_candidates
+        let eta = self
+            .eta_factor_of_vertex(p, vertex, candidates, current_clique)
+            .powf(p.beta);
+        tau * eta
     }
 
     fn choose_vertex_using_pheromones_probabilities(
         &self,
-        p: &mut ACOParameters,
+        p: &ACOParameters,
+        rng: &mut StdRng,
         candidates: &HashSet<usize>,
-        current_clique: &HashSet<usize>,
+        current_clique: &IncrementalClique,
     ) -> usize {
-        let mut probabilities: Vec<(usize, f64)> = vec![(0, 0.0); candidates.len()];
-
-        let mut memory_tau = vec![-1.0; p.graph.n_vertex];
-        let mut memory_sum_tau_candidates = 0.0;
-        for other_v in candidates.iter() {
-            let tau_other_v: f64 = if memory_tau[*other_v] > 0.0 {
-                memory_tau[*other_v]
-            } else {
-                self.tau_factor_of_vertex(other_v, current_clique)
-            };
+        // the hash set order changes between sets, sorting keeps the choice reproducible
+        let mut sorted_candidates = candidates.iter().collect::<Vec<&usize>>();
+        sorted_candidates.sort_unstable();
 
-            memory_tau[*other_v] = tau_other_v;
-            memory_sum_tau_candidates += tau_other_v;
+        let mut weights: Vec<(usize, f64)> = Vec::with_capacity(candidates.len());
+        let mut sum_weights = 0.0;
+        for candidate in sorted_candidates {
+            sum_weights += self.vertex_weight(p, candidate, candidates, current_clique);
+            weights.push((*candidate, sum_weights));
         }
 
-        let mut sum_propapilites = 0.0;
-        for (i, candidate) in candidates.iter().enumerate() {
-            let p = self.vertex_probability(
-                p,
-                candidate,
-                current_clique,
-                &mut memory_tau,
-                &memory_sum_tau_candidates,
-            );
-            sum_propapilites += p;
-            probabilities[i] = (*candidate, sum_propapilites);
+        let random: f64 = rng.gen_range(0.0..1.0) * sum_weights;
+        for weight in &weights {
+            if random <= weight.1 {
+                return weight.0;
+            }
         }
 
-        let random: f64 = p.rand.gen_range(0.0..1.0);
-        // println!("{}", random);
-        for prob in &probabilities {
-            if random <= prob.1 {
-                return prob.0;
+        weights.last().unwrap().0
+    }
+
+    fn construct_clique(&self, p: &ACOParameters, rng: &mut StdRng) -> HashSet<usize> {
+        let initial_vertex = p.graph.select_random_vertex(rng);
+        let mut k_clique = IncrementalClique::new(&p.graph, initial_vertex);
+        let mut candidates = p.graph.get_neighbor_candidates(initial_vertex);
+
+        while !candidates.is_empty() {
+            let new_v =
+                self.choose_vertex_using_pheromones_probabilities(p, rng, &candidates, &k_clique);
+            // the clique only grows when its clause still accepts every positive
+            if k_clique.try_add(&p.graph, new_v) {
+                // println!("new_v: {}, current_clique: {:?}", new_v, &k_clique.clique);
+                let new_v_candidates = p.graph.get_neighbor_candidates(new_v);
+                candidates = candidates
+                    .intersection(&new_v_candidates)
+                    .copied()
+                    .collect();
+            } else {
+                candidates.remove(&new_v);
             }
         }
 
-        probabilities.last().unwrap().0
+        k_clique.clique
     }
 
-    fn aco_procedure(&mut self, p: &mut ACOParameters) -> HashSet<usize> {
+    // one clique per seed, in the order of the seeds, split across p.threads threads.
+    // the threads are scoped to one generation since they borrow the trails, which
+    // the generation updates afterwards; spawning is cheap next to building cliques
+    fn construct_ant_cliques(&self, p: &ACOParameters, ant_seeds: &[u64]) -> Vec<HashSet<usize>> {
+        let construct = |seed: &u64| self.construct_clique(p, &mut StdRng::seed_from_u64(*seed));
+
+        let threads = p.threads.clamp(1, ant_seeds.len().max(1));
+        if threads == 1 {
+            return ant_seeds.iter().map(construct).collect();
+        }
+
+        let chunk_size = ant_seeds.len().div_ceil(threads);
+        thread::scope(|scope| {
+            let handles = ant_seeds
+                .chunks(chunk_size)
+                .map(|chunk| scope.spawn(move || chunk.iter().map(construct).collect::<Vec<_>>()))
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        })
+    }
+
+    // largest clique the available vertices allow: a vertex and all its candidates
+    fn clique_size_upper_bound(p: &ACOParameters) -> usize {
+        p.graph
+            .available_vertex
+            .iter()
+            .map(|v| p.graph.get_neighbor_candidates(*v).len() + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn aco_procedure(&mut self, p: &mut ACOParameters) -> (HashSet<usize>, StopCriterion) {
+        let start = Instant::now();
+        let upper_bound = Self::clique_size_upper_bound(p);
         let mut global_best: HashSet<usize> = HashSet::new();
+        let mut stagnant_cycles = 0;
+        let mut cycles_without_improvement = 0;
 
         for _gen in 0..p.cycles {
+            // every ant draws from its own stream, so the cliques do not depend on the threads
+            let ant_seeds: Vec<u64> = (0..p.ants).map(|_| p.rand.gen()).collect();
+            let ant_cliques = self.construct_ant_cliques(p, &ant_seeds);
+
             let mut gen_best: HashSet<usize> = HashSet::new();
+            for k_clique in &ant_cliques {
+                gen_best = Self::choose_best_clique(p, &gen_best, k_clique);
+            }
 
-            for _k in 0..p.ants {
-                let initial_vertex = p.graph.select_random_vertex();
-                let mut k_clique: HashSet<usize> = HashSet::from_iter(vec![initial_vertex]);
-                let mut candidates = p.graph.get_neighbor_candidates(initial_vertex);
+            if gen_best.len() > global_best.len() {
+                stagnant_cycles = 0;
+                cycles_without_improvement = 0;
+            } else {
+                stagnant_cycles += 1;
+                cycles_without_improvement += 1;
+            }
+            global_best = Self::choose_best_clique(p, &global_best, &gen_best);
+            self.update_pheromone_trail(p, &global_best, &gen_best, &ant_cliques);
 
-                while !candidates.is_empty() {
-                    let new_v = self.choose_vertex_using_pheromones_probabilities(
-                        p,
-                        &candidates,
-                        &k_clique,
-                    );
-                    let new_v_is_semantically_valid =
-                        self.candidate_is_semantically_valid(&p, &new_v, &k_clique);
-                    if new_v_is_semantically_valid {
-                        // println!("new_v: {}, current_clique: {:?}", new_v, &k_clique);
-                        let new_v_candidates = p.graph.get_neighbor_candidates(new_v);
-                        k_clique.insert(new_v);
-                        candidates = candidates
-                            .intersection(&new_v_candidates)
-                            .copied()
-                            .collect();
-                    } else {
-                        candidates.remove(&new_v);
-                    }
+            if let PheromoneUpdate::MaxMin(max_stagnant_cycles) = p.pheromone_update {
+                if stagnant_cycles >= max_stagnant_cycles {
+                    self.set_initial_pheromone_trails(p);
+                    stagnant_cycles = 0;
                 }
-
-                gen_best = Self::choose_best_clique(p, &gen_best, &k_clique);
             }
 
-            global_best = Self::choose_best_clique(p, &global_best, &gen_best);
-            self.update_pheromone_trail(p, &global_best, &gen_best);
-
             // println!("Generation {} |{}| -> {:?}", _gen, global_best.len(), global_best);
+
+            if global_best.len() >= upper_bound {
+                return (global_best, StopCriterion::UpperBound);
+            }
+            if p.max_cycles_without_improvement
+                .is_some_and(|max| cycles_without_improvement >= max)
+            {
+                return (global_best, StopCriterion::NoImprovement);
+            }
+            if p.time_budget
+                .is_some_and(|budget| start.elapsed() >= budget)
+            {
+                return (global_best, StopCriterion::TimeBudget);
+            }
         }
 
-        global_best
+        (global_best, StopCriterion::MaxCycles)
     }
 
-    fn candidate_is_semantically_valid(
-        &self,
-        p: &ACOParameters,
-        candidate: &usize,
-        current_clique: &HashSet<usize>,
-    ) -> bool {
-        let mut is_valid: bool = true;
-
-        // check if new cliques clause is complete
-        let mut new_clique = current_clique.clone();
-        new_clique.insert(*candidate);
-        let new_clique_clause = p.graph.get_clique_clause(new_clique);
-        // loop every positive element
-        // println!("Trying to add {} to {:?} -> {}", candidate, current_clique, &new_clique_clause);
-        for positive in &p.graph.positive_dataset {
-            let mut is_valid_one_positive = false;
-            let intersect = new_clique_clause.intersection(&positive.attributes);
-            // println!("  - Positive: {} -> {}", positive, &intersect);
-            for i in intersect.list {
-                is_valid_one_positive = is_valid_one_positive || !i.is_empty();
-                // println!("    - {} {} -> {}", is_valid_one_positive, i, !i.is_empty());
-            }
-
-            is_valid = is_valid && is_valid_one_positive;
-        }
-        // println!(
-        //     "Tryed to add {} to {:?} -> {} {}",
-        //     candidate, current_clique, &new_clique_clause, is_valid
-        // );
+    // cover the rejectability graph with cliques, every clique becomes a clause of the model.
+    // the ants search p.graph, so the graph being covered is lent back to p for every search
+    fn clique_cover(&mut self, p: &mut ACOParameters) -> CnfModel {
+        let mut graph = mem::replace(&mut p.graph, Graph::new(0, vec![], vec![], vec![]));
+        let model = clique_cover::clique_cover(&mut graph, |graph| {
+            mem::swap(graph, &mut p.graph);
+            let (best_clique, stop_criterion) = self.aco_procedure(p);
+            mem::swap(graph, &mut p.graph);
+            println!("Clique search stopped by: {}", stop_criterion);
+            best_clique
+        });
+        p.graph = graph;
 
-        is_valid
+        model
     }
 
     fn choose_best_clique(
@@ -189,3 +299,255 @@ pub trait ACO {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, time::Duration};
+
+    use crate::models::{
+        ant_colony_optimization::{
+            aco::{StopCriterion, ACO},
+            aco_parameters::{ACOHeuristic, ACOParameters, PheromoneUpdate},
+            edge_ac::EdgeAC,
+            vertex_ac::VertexAC,
+        },
+        data_handling::attribute_values_set::{AttributeValuesSet, AttributeValuesSetList},
+        data_handling::dataset::Dataset,
+        graph::incremental_clique::IncrementalClique,
+        graph::rejectability::create_rejectability_graph,
+        graph::rejectability_graph::{Graph, NoiseTolerance},
+    };
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    // vertex 1 is linked to both other candidates, 2 and 3 only to vertex 1
+    fn star_parameters(beta: f64) -> ACOParameters {
+        let mut p = ACOParameters::new(1, 1, 1.0, 0.99, 6.0, 0.01, 1000);
+        p.graph = Graph::new(4, vec![], vec![], vec![]);
+        let clause = AttributeValuesSetList::from_vec(vec![AttributeValuesSet::Cat(
+            "a".to_string(),
+            HashSet::new(),
+        )]);
+        for (u, v) in [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3)] {
+            p.graph.add_edge(u, v, &clause);
+        }
+        p.beta = beta;
+        p
+    }
+
+    #[test]
+    fn test_degree_heuristic() {
+        let p = star_parameters(1.0);
+        let vertex_ac = VertexAC::new(&p);
+        let candidates = HashSet::from([1, 2, 3]);
+        let clique = IncrementalClique::new(&p.graph, 0);
+
+        assert_eq!(p.heuristic, ACOHeuristic::Degree);
+        assert_eq!(
+            vertex_ac.eta_factor_of_vertex(&p, &1, &candidates, &clique),
+            3.0
+        );
+        assert_eq!(
+            vertex_ac.eta_factor_of_vertex(&p, &2, &candidates, &clique),
+            2.0
+        );
+        assert_eq!(vertex_ac.vertex_weight(&p, &1, &candidates, &clique), 18.0);
+    }
+
+    #[test]
+    fn test_beta_steers_the_choice() {
+        let candidates = HashSet::from([1, 2, 3]);
+        let count_hub_choices = |beta: f64| {
+            let p = star_parameters(beta);
+            let clique = IncrementalClique::new(&p.graph, 0);
+            let mut rng = p.rand.clone();
+            let vertex_ac = VertexAC::new(&p);
+            (0..200)
+                .filter(|_| {
+                    vertex_ac.choose_vertex_using_pheromones_probabilities(
+                        &p,
+                        &mut rng,
+                        &candidates,
+                        &clique,
+                    ) == 1
+                })
+                .count()
+        };
+
+        // equal pheromones: roughly a third of the choices without the heuristic
+        assert!(count_hub_choices(0.0) < 120);
+        assert!(count_hub_choices(20.0) > 190);
+    }
+
+    #[test]
+    fn test_clause_values_heuristic() {
+        let rng = StdRng::seed_from_u64(1000);
+        let dataset = Dataset::new(rng, "datasets/test2.csv", "class", "perfect", 80).unwrap();
+        let mut p = ACOParameters::new(1, 1, 1.0, 0.99, 6.0, 0.01, 1000);
+        p.graph = create_rejectability_graph(&dataset, 1, NoiseTolerance::default());
+        p.heuristic = ACOHeuristic::ClauseValues;
+        let vertex_ac = VertexAC::new(&p);
+
+        // the values kept by the clause of the clique with the candidate, while the
+        // clique grows
+        for vertex in 0..p.graph.n_vertex {
+            let mut clique = IncrementalClique::new(&p.graph, vertex);
+            let mut candidates = p.graph.get_neighbor_candidates(vertex);
+            let mut sorted_candidates = candidates.iter().copied().collect::<Vec<usize>>();
+            sorted_candidates.sort_unstable();
+            for candidate in sorted_candidates {
+                if !candidates.contains(&candidate) {
+                    continue;
+                }
+                let mut new_clique = clique.clique.clone();
+                new_clique.insert(candidate);
+                assert_eq!(
+                    vertex_ac.eta_factor_of_vertex(&p, &candidate, &candidates, &clique),
+                    1.0 + p.graph.get_encoded_clique_clause(&new_clique).n_values() as f64
+                );
+                if clique.try_add(&p.graph, candidate) {
+                    let neighbors = p.graph.get_neighbor_candidates(candidate);
+                    candidates = candidates.intersection(&neighbors).copied().collect();
+                }
+            }
+        }
+    }
+
+    // pheromone of every vertex after one update from trails at tau max, with rho 0.5
+    fn pheromones_after_update(pheromone_update: PheromoneUpdate) -> Vec<f64> {
+        let mut p = ACOParameters::new(1, 1, 1.0, 0.5, 6.0, 0.01, 1000);
+        p.graph = Graph::new(7, vec![], vec![], vec![]);
+        p.pheromone_update = pheromone_update;
+        let mut vertex_ac = VertexAC::new(&p);
+
+        let global_best = HashSet::from([2, 3, 4]);
+        let ant_cliques = vec![
+            HashSet::from([5]),
+            HashSet::from([0, 1]),
+            HashSet::from([6]),
+        ];
+        vertex_ac.update_pheromone_trail(&p, &global_best, &ant_cliques[1], &ant_cliques);
+
+        let no_clique = HashSet::new();
+        (0..7)
+            .map(|v| vertex_ac.tau_factor_of_vertex(&v, &no_clique))
+            .collect()
+    }
+
+    fn assert_pheromones(actual: Vec<f64>, expected: Vec<f64>) {
+        for (a, e) in actual.iter().zip(&expected) {
+            assert!((a - e).abs() < 1e-9, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_pheromone_update_strategies() {
+        assert_pheromones(
+            pheromones_after_update(PheromoneUpdate::IterationBest),
+            vec![3.5, 3.5, 3.0, 3.0, 3.0, 3.0, 3.0],
+        );
+        assert_pheromones(
+            pheromones_after_update(PheromoneUpdate::GlobalBest),
+            vec![3.0, 3.0, 4.0, 4.0, 4.0, 3.0, 3.0],
+        );
+        assert_pheromones(
+            pheromones_after_update(PheromoneUpdate::Elitist),
+            vec![3.5, 3.5, 4.0, 4.0, 4.0, 3.0, 3.0],
+        );
+        // {0, 1} ranks first with weight 2/3, {5} second with weight 1/3, {6} is left out
+        assert_pheromones(
+            pheromones_after_update(PheromoneUpdate::RankBased(3)),
+            vec![
+                3.0 + 1.0 / 3.0,
+                3.0 + 1.0 / 3.0,
+                4.0,
+                4.0,
+                4.0,
+                3.0 + 1.0 / 9.0,
+                3.0,
+            ],
+        );
+        assert_pheromones(
+            pheromones_after_update(PheromoneUpdate::MaxMin(5)),
+            vec![3.5, 3.5, 3.0, 3.0, 3.0, 3.0, 3.0],
+        );
+    }
+
+    #[test]
+    fn test_max_min_resets_stagnant_trails() {
+        let no_clique = HashSet::new();
+        let pheromones_after_search = |pheromone_update: PheromoneUpdate| {
+            let mut p = star_parameters(0.0);
+            p.cycles = 4;
+            p.rho = 0.5;
+            p.pheromone_update = pheromone_update;
+            let mut vertex_ac = VertexAC::new(&p);
+            let (_, stop_criterion) = vertex_ac.aco_procedure(&mut p);
+            assert_eq!(stop_criterion, StopCriterion::MaxCycles);
+            (0..4)
+                .map(|v| vertex_ac.tau_factor_of_vertex(&v, &no_clique))
+                .collect::<Vec<f64>>()
+        };
+
+        // every ant finds a maximum clique, so the search stagnates after one generation
+        assert!(pheromones_after_search(PheromoneUpdate::IterationBest)
+            .iter()
+            .any(|tau| *tau < 6.0));
+        assert_eq!(
+            pheromones_after_search(PheromoneUpdate::MaxMin(1)),
+            vec![6.0; 4]
+        );
+    }
+
+    #[test]
+    fn test_stop_criteria() {
+        let mut p = star_parameters(0.0);
+        p.cycles = 100;
+        p.max_cycles_without_improvement = Some(3);
+        let (clique, stop_criterion) = VertexAC::new(&p).aco_procedure(&mut p);
+        assert_eq!(clique.len(), 3);
+        assert_eq!(stop_criterion, StopCriterion::NoImprovement);
+
+        let mut p = star_parameters(0.0);
+        p.cycles = 100;
+        p.time_budget = Some(Duration::ZERO);
+        let (_, stop_criterion) = VertexAC::new(&p).aco_procedure(&mut p);
+        assert_eq!(stop_criterion, StopCriterion::TimeBudget);
+
+        // without vertex 3 the star is a triangle, found by the first ant
+        let mut p = star_parameters(0.0);
+        p.cycles = 100;
+        p.graph
+            .remove_vertex_set_from_available(&HashSet::from([3]));
+        let (clique, stop_criterion) = VertexAC::new(&p).aco_procedure(&mut p);
+        assert_eq!(clique, HashSet::from([0, 1, 2]));
+        assert_eq!(stop_criterion, StopCriterion::UpperBound);
+    }
+
+    #[test]
+    fn test_ant_cliques_do_not_depend_on_threads() {
+        let rng = StdRng::seed_from_u64(1000);
+        let dataset =
+            Dataset::new(rng.clone(), "datasets/test2.csv", "class", "perfect", 80).unwrap();
+        let graph = create_rejectability_graph(&dataset, 1, NoiseTolerance::default());
+
+        let cliques_with_threads = |threads: usize| {
+            let mut p = ACOParameters::new(3, 7, 1.0, 0.99, 6.0, 0.01, 1000);
+            p.graph = graph.clone();
+            p.threads = threads;
+            let edge_ac = EdgeAC::new(&p);
+            let ant_seeds: Vec<u64> = (0..p.ants as u64).collect();
+            (
+                edge_ac.construct_ant_cliques(&p, &ant_seeds),
+                EdgeAC::new(&p).clique_cover(&mut p),
+            )
+        };
+
+        let (sequential_cliques, sequential_model) = cliques_with_threads(1);
+        for threads in [2, 3, 16] {
+            let (cliques, model) = cliques_with_threads(threads);
+            assert_eq!(cliques, sequential_cliques);
+            assert_eq!(model, sequential_model);
+        }
+    }
+}
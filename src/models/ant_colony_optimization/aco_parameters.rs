@@ -1,10 +1,10 @@
+use std::time::Duration;
+
 use rand::{rngs::StdRng, SeedableRng};
 
-use crate::models::{
-    data_handling::attribute_values_set::AttributeValuesSetList, graph::rejectability_graph::Graph,
-};
+use crate::models::graph::rejectability_graph::Graph;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ACOParameters {
     pub graph: Graph,
     pub rand: StdRng,
@@ -14,14 +14,47 @@ pub struct ACOParameters {
     pub rho: f64,
     pub tau_max: f64,
     pub tau_min: f64,
+    pub heuristic: ACOHeuristic,
+    pub beta: f64,
+    pub pheromone_update: PheromoneUpdate,
+    // stop a clique search after this many generations without a larger clique
+    pub max_cycles_without_improvement: Option<usize>,
+    // stop a clique search once it has run for this long
+    pub time_budget: Option<Duration>,
+    // threads building the ant cliques of a generation
+    pub threads: usize,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ACOAlgorithm {
     VertexAC,
     EdgeAC,
 }
 
+// desirability of a candidate, independent of the pheromones
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ACOHeuristic {
+    // 1 + neighbors of the candidate among the other candidates
+    Degree,
+    // 1 + values kept by the clique clause once the candidate is added
+    ClauseValues,
+}
+
+// cliques that deposit pheromone at the end of every generation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PheromoneUpdate {
+    // best clique of the generation
+    IterationBest,
+    // best clique found so far
+    GlobalBest,
+    // both the generation best and the best so far
+    Elitist,
+    // best so far plus the w - 1 best ants of the generation, weighted by rank
+    RankBased(usize),
+    // generation best, trails are reset to tau max after n generations without improvement
+    MaxMin(usize),
+}
+
 impl ACOParameters {
     pub fn new(
         cycles: usize,
@@ -30,10 +63,11 @@ impl ACOParameters {
         rho: f64,
         tau_max: f64,
         tau_min: f64,
+        seed: u64,
     ) -> ACOParameters {
-        let rng = StdRng::seed_from_u64(1000);
+        let rng = StdRng::seed_from_u64(seed);
         ACOParameters {
-            graph: Graph::new(rng.clone(), 0, vec![], vec![]),
+            graph: Graph::new(0, vec![], vec![], vec![]),
             rand: rng,
             cycles,
             ants,
@@ -41,6 +75,12 @@ impl ACOParameters {
             rho,
             tau_max,
             tau_min,
+            heuristic: ACOHeuristic::Degree,
+            beta: 0.0,
+            pheromone_update: PheromoneUpdate::IterationBest,
+            max_cycles_without_improvement: None,
+            time_budget: None,
+            threads: 1,
         }
     }
 }
@@ -37,12 +37,13 @@ impl ACO for EdgeAC {
         pheromone_delta: &f64,
         current_clique: &HashSet<usize>,
     ) {
-        for i in 0..current_clique.len() {
-            for j in 0..current_clique.len() {
+        // deposit on both directions of every edge between two clique vertices
+        for i in current_clique {
+            for j in current_clique {
                 if i != j {
-                    self.pheromones[i][j] += pheromone_delta;
-                    if self.pheromones[i][j] > p.tau_max {
-                        self.pheromones[i][j] = p.tau_max;
+                    self.pheromones[*i][*j] += pheromone_delta;
+                    if self.pheromones[*i][*j] > p.tau_max {
+                        self.pheromones[*i][*j] = p.tau_max;
                     }
                 }
             }
@@ -60,3 +61,42 @@ impl ACO for EdgeAC {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::models::{
+        ant_colony_optimization::{aco::ACO, aco_parameters::ACOParameters},
+        graph::rejectability_graph::Graph,
+    };
+
+    use super::EdgeAC;
+
+    #[test]
+    fn test_increment_pheromone_on_clique_edges() {
+        let mut p = ACOParameters::new(1, 1, 1.0, 0.5, 6.0, 0.01, 1000);
+        p.graph = Graph::new(6, vec![], vec![], vec![]);
+        let mut edge_ac = EdgeAC::new(&p);
+        edge_ac.decrement_pheromone(&p);
+
+        let clique = HashSet::from([2, 4, 5]);
+        edge_ac.increment_pheromone(&p, &1.0, &clique);
+
+        for i in 0..6 {
+            for j in 0..6 {
+                let expected = if i != j && clique.contains(&i) && clique.contains(&j) {
+                    4.0
+                } else {
+                    3.0
+                };
+                assert_eq!(edge_ac.pheromones[i][j], expected, "edge ({}, {})", i, j);
+            }
+        }
+
+        // the deposit never goes over tau max
+        edge_ac.increment_pheromone(&p, &5.0, &clique);
+        assert_eq!(edge_ac.pheromones[2][5], 6.0);
+        assert_eq!(edge_ac.pheromones[0][1], 3.0);
+    }
+}
@@ -28,12 +28,12 @@ impl ACO for VertexAC {
         &mut self,
         p: &ACOParameters,
         pheromone_delta: &f64,
-        _current_clique: &HashSet<usize>,
+        current_clique: &HashSet<usize>,
     ) {
-        for i in 0..self.pheromones.len() {
-            self.pheromones[i] += pheromone_delta;
-            if self.pheromones[i] > p.tau_max {
-                self.pheromones[i] = p.tau_max;
+        for i in current_clique {
+            self.pheromones[*i] += pheromone_delta;
+            if self.pheromones[*i] > p.tau_max {
+                self.pheromones[*i] = p.tau_max;
             }
         }
     }
@@ -47,3 +47,32 @@ impl ACO for VertexAC {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::models::{
+        ant_colony_optimization::{aco::ACO, aco_parameters::ACOParameters},
+        graph::rejectability_graph::Graph,
+    };
+
+    use super::VertexAC;
+
+    #[test]
+    fn test_increment_pheromone_on_clique_vertices() {
+        let mut p = ACOParameters::new(1, 1, 1.0, 0.5, 6.0, 0.01, 1000);
+        p.graph = Graph::new(6, vec![], vec![], vec![]);
+        let mut vertex_ac = VertexAC::new(&p);
+        vertex_ac.decrement_pheromone(&p);
+
+        let clique = HashSet::from([1, 3]);
+        vertex_ac.increment_pheromone(&p, &1.0, &clique);
+
+        assert_eq!(vertex_ac.pheromones, vec![3.0, 4.0, 3.0, 4.0, 3.0, 3.0]);
+
+        // the deposit never goes over tau max
+        vertex_ac.increment_pheromone(&p, &5.0, &clique);
+        assert_eq!(vertex_ac.pheromones, vec![3.0, 6.0, 3.0, 6.0, 3.0, 3.0]);
+    }
+}
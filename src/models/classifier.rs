@@ -0,0 +1,9 @@
+pub mod cnf_model;
+pub mod dnf_model;
+pub mod model_file;
+pub mod multi_class;
+pub mod prediction;
+pub mod pruning;
+pub mod rule_model;
+pub mod training;
+pub mod unit_tests;
@@ -0,0 +1,53 @@
+use std::fmt;
+
+use crate::models::{clause::DisjunctiveClause, data_handling::row::Row};
+
+// Conjunction of disjunctive clauses, one per clique of the cover.
+// Every clause rejects the negatives of its clique, so a row is
+// classified as positive only when all the clauses accept it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CnfModel {
+    pub clauses: Vec<DisjunctiveClause>,
+}
+
+impl fmt::Display for CnfModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, clause) in self.clauses.iter().enumerate() {
+            write!(f, "{}", clause)?;
+            if idx + 1 < self.clauses.len() {
+                write!(f, " ∧ ")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CnfModel {
+    pub fn new(clauses: Vec<DisjunctiveClause>) -> CnfModel {
+        CnfModel { clauses }
+    }
+
+    pub fn len(&self) -> usize {
+        self.clauses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+    }
+
+    // number of selectors in all the clauses
+    pub fn n_selectors(&self) -> usize {
+        self.clauses
+            .iter()
+            .map(|clause| clause.selectors.len())
+            .sum()
+    }
+
+    pub fn add_clause(&mut self, clause: DisjunctiveClause) {
+        self.clauses.push(clause);
+    }
+
+    pub fn predict(&self, row: &Row) -> bool {
+        self.clauses.iter().all(|clause| clause.accepts(row))
+    }
+}
@@ -0,0 +1,64 @@
+use std::fmt;
+
+use crate::models::{clause::ConjunctiveTerm, data_handling::row::Row};
+
+use super::cnf_model::CnfModel;
+
+// Disjunction of conjunctive terms, read as "if A and B then positive".
+// It is learned as the CNF model of the dataset with the classes swapped:
+// a row rejected by that model is positive, so every clause negated gives
+// a term and a row is classified as positive when any term accepts it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DnfModel {
+    pub terms: Vec<ConjunctiveTerm>,
+}
+
+impl fmt::Display for DnfModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, term) in self.terms.iter().enumerate() {
+            write!(f, "{}", term)?;
+            if idx + 1 < self.terms.len() {
+                write!(f, " ∨ ")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl DnfModel {
+    pub fn new(terms: Vec<ConjunctiveTerm>) -> DnfModel {
+        DnfModel { terms }
+    }
+
+    // model accepting exactly the rows the CNF model rejects
+    pub fn negation_of(model: &CnfModel) -> DnfModel {
+        DnfModel::new(
+            model
+                .clauses
+                .iter()
+                .map(|clause| clause.negation())
+                .collect(),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    // number of selectors in all the terms
+    pub fn n_selectors(&self) -> usize {
+        self.terms.iter().map(|term| term.selectors.len()).sum()
+    }
+
+    pub fn add_term(&mut self, term: ConjunctiveTerm) {
+        self.terms.push(term);
+    }
+
+    pub fn predict(&self, row: &Row) -> bool {
+        self.terms.iter().any(|term| term.accepts(row))
+    }
+}
@@ -0,0 +1,334 @@
+use std::{error::Error, fmt, fs};
+
+use crate::models::{
+    clause::{ConjunctiveTerm, DisjunctiveClause, Selector},
+    data_handling::attribute_kind::AttributeKind,
+};
+
+use super::{
+    cnf_model::CnfModel,
+    dnf_model::DnfModel,
+    rule_model::{RuleForm, RuleModel},
+};
+
+// first line of every model file, the number is bumped on incompatible changes
+const HEADER: &str = "oqat-model 2";
+
+// version 1 files hold a CNF model without the form, term and negated selector
+// records, they are still read
+const CNF_HEADER_V1: &str = "oqat-cnf-model 1";
+const RECORDS_SINCE_V2: [&str; 6] = ["form", "term", "neq", "lt", "gt", "outside"];
+
+// A learned model together with everything needed to apply it to new data.
+//
+// The file is plain text with one tab separated record per line:
+//
+//     oqat-model 2
+//     class-column    <name>
+//     positive-class  <value>
+//     seed            <u64>
+//     form            cnf|dnf                         (cnf when missing)
+//     attribute       <name>  numeric|categorical     (one per column, in order)
+//     parameter       <name>  <value>                 (one per training parameter)
+//     clause                                          (starts a new clause, cnf only)
+//     term                                            (starts a new term, dnf only)
+//     eq              <attribute>  <value>
+//     leq             <attribute>  <threshold>
+//     geq             <attribute>  <threshold>
+//     range           <attribute>  <low>  <high>
+//     neq             <attribute>  <value>
+//     lt              <attribute>  <threshold>
+//     gt              <attribute>  <threshold>
+//     outside         <attribute>  <low>  <high>
+//
+// Tabs, newlines and backslashes inside names and values are escaped as
+// `\t`, `\n` and `\\`. Thresholds are written with the shortest decimal
+// representation that parses back to the same f64.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModelFile {
+    pub model: RuleModel,
+    pub schema: Vec<(String, AttributeKind)>,
+    pub class_column: String,
+    pub positive_class: String,
+    pub parameters: Vec<(String, String)>,
+    pub seed: u64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ModelFileError {
+    // path and reason of a file that could not be read or written
+    Io(String, String),
+    // line number and reason of a malformed record
+    Parse(usize, String),
+    // name of a required record the file does not have
+    Missing(String),
+}
+
+impl fmt::Display for ModelFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ModelFileError::Io(path, reason) => write!(f, "Model file '{}': {}", path, reason),
+            ModelFileError::Parse(line, reason) => {
+                write!(f, "Model file line {}: {}", line, reason)
+            }
+            ModelFileError::Missing(name) => write!(f, "Model file has no '{}' record", name),
+        }
+    }
+}
+
+impl Error for ModelFileError {}
+
+impl fmt::Display for ModelFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+        writeln!(f, "class-column\t{}", escape(&self.class_column))?;
+        writeln!(f, "positive-class\t{}", escape(&self.positive_class))?;
+        writeln!(f, "seed\t{}", self.seed)?;
+        match self.model.form() {
+            RuleForm::Cnf => writeln!(f, "form\tcnf")?,
+            RuleForm::Dnf => writeln!(f, "form\tdnf")?,
+        }
+        for (name, kind) in &self.schema {
+            writeln!(f, "attribute\t{}\t{}", escape(name), kind)?;
+        }
+        for (name, value) in &self.parameters {
+            writeln!(f, "parameter\t{}\t{}", escape(name), escape(value))?;
+        }
+        match &self.model {
+            RuleModel::Cnf(model) => {
+                for clause in &model.clauses {
+                    writeln!(f, "clause")?;
+                    write_selectors(f, &clause.selectors)?;
+                }
+            }
+            RuleModel::Dnf(model) => {
+                for term in &model.terms {
+                    writeln!(f, "term")?;
+                    write_selectors(f, &term.selectors)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn write_selectors(f: &mut fmt::Formatter, selectors: &[Selector]) -> fmt::Result {
+    for selector in selectors {
+        match selector {
+            Selector::Eq(attr, value) => writeln!(f, "eq\t{}\t{}", escape(attr), escape(value))?,
+            Selector::Leq(attr, value) => writeln!(f, "leq\t{}\t{}", escape(attr), value)?,
+            Selector::Geq(attr, value) => writeln!(f, "geq\t{}\t{}", escape(attr), value)?,
+            Selector::Range(attr, low, high) => {
+                writeln!(f, "range\t{}\t{}\t{}", escape(attr), low, high)?
+            }
+            Selector::Neq(attr, value) => writeln!(f, "neq\t{}\t{}", escape(attr), escape(value))?,
+            Selector::Lt(attr, value) => writeln!(f, "lt\t{}\t{}", escape(attr), value)?,
+            Selector::Gt(attr, value) => writeln!(f, "gt\t{}\t{}", escape(attr), value)?,
+            Selector::Outside(attr, low, high) => {
+                writeln!(f, "outside\t{}\t{}\t{}", escape(attr), low, high)?
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl ModelFile {
+    pub fn save(&self, path: &str) -> Result<(), ModelFileError> {
+        fs::write(path, self.to_string())
+            .map_err(|err| ModelFileError::Io(path.to_string(), err.to_string()))
+    }
+
+    pub fn load(path: &str) -> Result<ModelFile, ModelFileError> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| ModelFileError::Io(path.to_string(), err.to_string()))?;
+        ModelFile::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<ModelFile, ModelFileError> {
+        let mut lines = content.lines().enumerate();
+        let version_1 = match lines.next() {
+            Some((_, HEADER)) => false,
+            Some((_, CNF_HEADER_V1)) => true,
+            _ => {
+                return Err(ModelFileError::Parse(
+                    1,
+                    format!("expected header '{}'", HEADER),
+                ))
+            }
+        };
+
+        let (mut class_column, mut positive_class, mut seed) = (None, None, None);
+        let mut model_file = ModelFile {
+            model: RuleModel::Cnf(CnfModel::new(vec![])),
+            schema: vec![],
+            class_column: String::new(),
+            positive_class: String::new(),
+            parameters: vec![],
+            seed: 0,
+        };
+
+        for (idx, line) in lines {
+            let line_number = idx + 1;
+            let error = |reason: String| ModelFileError::Parse(line_number, reason);
+            let fields = line.split('\t').map(unescape).collect::<Vec<String>>();
+            let field_count = |expected: usize| {
+                if fields.len() == expected {
+                    Ok(())
+                } else {
+                    Err(error(format!(
+                        "expected {} fields for '{}', found {}",
+                        expected,
+                        fields[0],
+                        fields.len()
+                    )))
+                }
+            };
+            let float = |value: &str| {
+                value
+                    .parse::<f64>()
+                    .map_err(|_| error(format!("invalid threshold '{}'", value)))
+            };
+
+            if version_1 && RECORDS_SINCE_V2.contains(&fields[0].as_str()) {
+                return Err(error(format!("unknown record '{}'", fields[0])));
+            }
+
+            match fields[0].as_str() {
+                "" if line.is_empty() => continue,
+                "class-column" => {
+                    field_count(2)?;
+                    class_column = Some(fields[1].clone());
+                }
+                "positive-class" => {
+                    field_count(2)?;
+                    positive_class = Some(fields[1].clone());
+                }
+                "seed" => {
+                    field_count(2)?;
+                    seed = Some(
+                        fields[1]
+                            .parse::<u64>()
+                            .map_err(|_| error(format!("invalid seed '{}'", fields[1])))?,
+                    );
+                }
+                "form" => {
+                    field_count(2)?;
+                    if !model_file.model.is_empty() {
+                        return Err(error("form record after the first clause".to_string()));
+                    }
+                    model_file.model = match fields[1].as_str() {
+                        "cnf" => RuleModel::Cnf(CnfModel::new(vec![])),
+                        "dnf" => RuleModel::Dnf(DnfModel::new(vec![])),
+                        other => return Err(error(format!("invalid form '{}'", other))),
+                    };
+                }
+                "attribute" => {
+                    field_count(3)?;
+                    let kind = fields[2].parse::<AttributeKind>().map_err(error)?;
+                    model_file.schema.push((fields[1].clone(), kind));
+                }
+                "parameter" => {
+                    field_count(3)?;
+                    model_file
+                        .parameters
+                        .push((fields[1].clone(), fields[2].clone()));
+                }
+                "clause" => {
+                    field_count(1)?;
+                    match &mut model_file.model {
+                        RuleModel::Cnf(model) => model.add_clause(DisjunctiveClause::new(vec![])),
+                        RuleModel::Dnf(_) => {
+                            return Err(error("clause record in a DNF model".to_string()))
+                        }
+                    }
+                }
+                "term" => {
+                    field_count(1)?;
+                    match &mut model_file.model {
+                        RuleModel::Dnf(model) => model.add_term(ConjunctiveTerm::new(vec![])),
+                        RuleModel::Cnf(_) => {
+                            return Err(error("term record in a CNF model".to_string()))
+                        }
+                    }
+                }
+                kind @ ("eq" | "leq" | "geq" | "range" | "neq" | "lt" | "gt" | "outside") => {
+                    let selector = match kind {
+                        "eq" | "neq" => {
+                            field_count(3)?;
+                            Selector::new_eq(fields[1].clone(), fields[2].clone())
+                        }
+                        "leq" | "gt" => {
+                            field_count(3)?;
+                            Selector::new_leq(fields[1].clone(), float(&fields[2])?)
+                        }
+                        "geq" | "lt" => {
+                            field_count(3)?;
+                            Selector::new_geq(fields[1].clone(), float(&fields[2])?)
+                        }
+                        _ => {
+                            field_count(4)?;
+                            Selector::new_range(
+                                fields[1].clone(),
+                                float(&fields[2])?,
+                                float(&fields[3])?,
+                            )
+                        }
+                    };
+                    // the records of negated selectors are read through their counterpart
+                    let selector = if matches!(kind, "neq" | "gt" | "lt" | "outside") {
+                        selector.negation()
+                    } else {
+                        selector
+                    };
+                    match &mut model_file.model {
+                        RuleModel::Cnf(model) => match model.clauses.last_mut() {
+                            Some(clause) => clause.selectors.push(selector),
+                            None => return Err(error("selector outside of a clause".to_string())),
+                        },
+                        RuleModel::Dnf(model) => match model.terms.last_mut() {
+                            Some(term) => term.selectors.push(selector),
+                            None => return Err(error("selector outside of a term".to_string())),
+                        },
+                    }
+                }
+                other => return Err(error(format!("unknown record '{}'", other))),
+            }
+        }
+
+        let missing = |name: &str| ModelFileError::Missing(name.to_string());
+        model_file.class_column = class_column.ok_or_else(|| missing("class-column"))?;
+        model_file.positive_class = positive_class.ok_or_else(|| missing("positive-class"))?;
+        model_file.seed = seed.ok_or_else(|| missing("seed"))?;
+
+        Ok(model_file)
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('t') => result.push('\t'),
+                Some('n') => result.push('\n'),
+                Some(other) => result.push(other),
+                None => result.push('\\'),
+            }
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
@@ -0,0 +1,142 @@
+use std::fmt;
+
+use rand::rngs::StdRng;
+
+use crate::models::{
+    config::Algorithm,
+    data_handling::{multi_class_dataset::MultiClassDataset, row::Row},
+};
+
+use super::{
+    rule_model::{RuleForm, RuleModel},
+    training::{train_model, TrainingOptions},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MultiClassStrategy {
+    // one model per class, learned against the rows of every other class
+    OneVsRest,
+    // one model per pair of classes, learned on the rows of the two classes only
+    OneVsOne,
+}
+
+// binary model of the classes of a multi-class model, the negative
+// class is None when the model was learned against every other class
+#[derive(Clone, Debug, PartialEq)]
+pub struct BinaryModel {
+    pub positive: usize,
+    pub negative: Option<usize>,
+    pub model: RuleModel,
+}
+
+// Every binary model votes for a class: a one-vs-rest model for its class
+// when it accepts the row, a one-vs-one model for its positive class when it
+// accepts the row and for its negative class otherwise. The class with the
+// most votes is predicted. Ties, including a row accepted by no one-vs-rest
+// model, go to the tied class with the most learning rows, then to the first
+// one by name.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultiClassModel {
+    // sorted by name
+    pub classes: Vec<String>,
+    // learning rows of every class, they break the ties
+    pub learning_counts: Vec<usize>,
+    pub models: Vec<BinaryModel>,
+}
+
+impl fmt::Display for MultiClassModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, binary) in self.models.iter().enumerate() {
+            let negative = binary
+                .negative
+                .map_or("rest", |negative| self.classes[negative].as_str());
+            write!(
+                f,
+                "{} vs {}: |{}| {}",
+                self.classes[binary.positive],
+                negative,
+                binary.model.len(),
+                binary.model
+            )?;
+            if idx + 1 < self.models.len() {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl MultiClassModel {
+    // votes of the binary models for every class
+    pub fn votes(&self, row: &Row) -> Vec<usize> {
+        let mut votes = vec![0; self.classes.len()];
+        for binary in &self.models {
+            match (binary.model.predict(row), binary.negative) {
+                (true, _) => votes[binary.positive] += 1,
+                (false, Some(negative)) => votes[negative] += 1,
+                (false, None) => {}
+            }
+        }
+        votes
+    }
+
+    pub fn predict(&self, row: &Row) -> &str {
+        let votes = self.votes(row);
+        // classes are sorted, so on equal counts min_by_key keeps the first name
+        let best = (0..self.classes.len())
+            .min_by_key(|class_idx| {
+                (
+                    std::cmp::Reverse(votes[*class_idx]),
+                    std::cmp::Reverse(self.learning_counts[*class_idx]),
+                )
+            })
+            .unwrap();
+        &self.classes[best]
+    }
+}
+
+// learn the binary models of the strategy, all of them in the same form
+pub fn train_multi_class_model(
+    rng: StdRng,
+    dataset: &MultiClassDataset,
+    strategy: MultiClassStrategy,
+    algorithm: &Algorithm,
+    training: &TrainingOptions,
+    form: RuleForm,
+) -> MultiClassModel {
+    let classes = &dataset.classes;
+    let pairs: Vec<(usize, Option<usize>)> = match strategy {
+        MultiClassStrategy::OneVsRest => (0..classes.len()).map(|idx| (idx, None)).collect(),
+        MultiClassStrategy::OneVsOne => (0..classes.len())
+            .flat_map(|positive| {
+                (positive + 1..classes.len()).map(move |negative| (positive, Some(negative)))
+            })
+            .collect(),
+    };
+
+    let models = pairs
+        .into_iter()
+        .map(|(positive, negative)| {
+            let binary_dataset = match negative {
+                Some(negative) => dataset.one_vs_one(&classes[positive], &classes[negative]),
+                None => dataset.one_vs_rest(&classes[positive]),
+            };
+            println!(
+                "Class {} vs {}",
+                classes[positive],
+                negative.map_or("rest", |negative| classes[negative].as_str())
+            );
+            BinaryModel {
+                positive,
+                negative,
+                model: train_model(rng.clone(), &binary_dataset, algorithm, training, form),
+            }
+        })
+        .collect();
+
+    MultiClassModel {
+        classes: classes.clone(),
+        learning_counts: dataset.learning_counts(),
+        models,
+    }
+}
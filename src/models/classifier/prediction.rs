@@ -0,0 +1,93 @@
+use std::{error::Error, io::Write};
+
+use crate::models::data_handling::{
+    attribute_values_set::{AttributeValuesSet, AttributeValuesSetList},
+    dataset_error::DatasetError,
+    row::Row,
+};
+
+use super::model_file::ModelFile;
+
+// Policy used when the input does not look exactly like the learning data:
+//  - columns are matched by name, so their order does not matter
+//  - columns that are not attributes of the model (including the class column)
+//    are ignored for the prediction and written back unchanged
+//  - attributes of the model missing from the input are an error, the row
+//    cannot be classified without them
+//  - an empty cell, or a non numeric value in a numeric column, simply satisfies
+//    none of the selectors of its attribute, negated ones of DNF models included
+//  - a category never seen during learning satisfies no [a=v] selector, and
+//    every [a≠v] selector of a DNF model since it differs from v
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PredictionSummary {
+    pub positive: usize,
+    pub negative: usize,
+}
+
+// read an unlabeled csv and write it back with the predicted class appended as a new column
+pub fn predict_csv<W: Write>(
+    model_file: &ModelFile,
+    input_path: &str,
+    output: W,
+    predicted_column: &str,
+    negative_label: &str,
+) -> Result<PredictionSummary, Box<dyn Error>> {
+    let csv_error = |err| DatasetError::from_csv(input_path, err);
+
+    let mut reader = csv::Reader::from_path(input_path).map_err(csv_error)?;
+    let headers = reader.headers().map_err(csv_error)?.clone();
+
+    let mut missing = vec![];
+    let mut attribute_columns = vec![];
+    for (name, _) in &model_file.schema {
+        match headers.iter().position(|header| header == name) {
+            Some(idx) => attribute_columns.push((name.clone(), idx)),
+            None => missing.push(name.clone()),
+        }
+    }
+    if !missing.is_empty() {
+        return Err(Box::new(DatasetError::SchemaMismatch(format!(
+            "columns [{}] of the model are missing from '{}'",
+            missing.join(", "),
+            input_path
+        ))));
+    }
+    if headers.iter().any(|header| header == predicted_column) {
+        return Err(Box::new(DatasetError::SchemaMismatch(format!(
+            "column '{}' already exists in '{}'",
+            predicted_column, input_path
+        ))));
+    }
+
+    let mut writer = csv::Writer::from_writer(output);
+    let mut output_headers = headers.clone();
+    output_headers.push_field(predicted_column);
+    writer.write_record(&output_headers)?;
+
+    let mut summary = PredictionSummary::default();
+    for result in reader.records() {
+        let mut record = result.map_err(csv_error)?;
+
+        let row = Row {
+            class: String::new(),
+            attributes: AttributeValuesSetList::from_vec(
+                attribute_columns
+                    .iter()
+                    .map(|(name, idx)| AttributeValuesSet::from_field(name, &record[*idx]))
+                    .collect(),
+            ),
+        };
+
+        if model_file.model.predict(&row) {
+            summary.positive += 1;
+            record.push_field(&model_file.positive_class);
+        } else {
+            summary.negative += 1;
+            record.push_field(negative_label);
+        }
+        writer.write_record(&record)?;
+    }
+    writer.flush()?;
+
+    Ok(summary)
+}
@@ -0,0 +1,105 @@
+use crate::models::{
+    clause::{DisjunctiveClause, Selector},
+    data_handling::row::Row,
+};
+
+use super::cnf_model::CnfModel;
+
+// simplify a model that separates the learning examples, keeping it separating them:
+// first every clause drops the selectors no positive needs, which only makes it
+// reject more negatives, then the clauses whose negatives are all rejected by
+// other clauses are dropped
+pub fn prune_model(model: &CnfModel, positives: &[Row], negatives: &[Row]) -> CnfModel {
+    let clauses = model
+        .clauses
+        .iter()
+        .map(|clause| prune_selectors(clause, positives))
+        .collect::<Vec<DisjunctiveClause>>();
+
+    CnfModel::new(drop_redundant_clauses(clauses, negatives))
+}
+
+// remove selectors while every positive is still accepted by the clause,
+// the selectors accepting the fewest positives are tried first
+pub fn prune_selectors(clause: &DisjunctiveClause, positives: &[Row]) -> DisjunctiveClause {
+    let accepted: Vec<Vec<bool>> = clause
+        .selectors
+        .iter()
+        .map(|selector| positives.iter().map(|row| selector.accepts(row)).collect())
+        .collect();
+    // number of kept selectors accepting every positive
+    let mut n_accepting: Vec<usize> = (0..positives.len())
+        .map(|pos_idx| accepted.iter().filter(|acc| acc[pos_idx]).count())
+        .collect();
+
+    let mut order = (0..clause.selectors.len()).collect::<Vec<usize>>();
+    order.sort_by_key(|sel_idx| accepted[*sel_idx].iter().filter(|acc| **acc).count());
+
+    let mut kept = vec![true; clause.selectors.len()];
+    for sel_idx in order {
+        let needed = accepted[sel_idx]
+            .iter()
+            .enumerate()
+            .any(|(pos_idx, acc)| *acc && n_accepting[pos_idx] == 1);
+        if !needed {
+            kept[sel_idx] = false;
+            for (pos_idx, acc) in accepted[sel_idx].iter().enumerate() {
+                if *acc {
+                    n_accepting[pos_idx] -= 1;
+                }
+            }
+        }
+    }
+
+    DisjunctiveClause::new(
+        clause
+            .selectors
+            .iter()
+            .zip(kept)
+            .filter(|(_, kept)| *kept)
+            .map(|(selector, _)| selector.clone())
+            .collect::<Vec<Selector>>(),
+    )
+}
+
+// remove clauses while every negative is still rejected by at least one clause,
+// the clauses rejecting the fewest negatives are tried first
+pub fn drop_redundant_clauses(
+    clauses: Vec<DisjunctiveClause>,
+    negatives: &[Row],
+) -> Vec<DisjunctiveClause> {
+    let rejected: Vec<Vec<bool>> = clauses
+        .iter()
+        .map(|clause| negatives.iter().map(|row| !clause.accepts(row)).collect())
+        .collect();
+    // number of kept clauses rejecting every negative
+    let mut n_rejecting: Vec<usize> = (0..negatives.len())
+        .map(|neg_idx| rejected.iter().filter(|rej| rej[neg_idx]).count())
+        .collect();
+
+    let mut order = (0..clauses.len()).collect::<Vec<usize>>();
+    order.sort_by_key(|clause_idx| rejected[*clause_idx].iter().filter(|rej| **rej).count());
+
+    let mut kept = vec![true; clauses.len()];
+    for clause_idx in order {
+        let needed = rejected[clause_idx]
+            .iter()
+            .enumerate()
+            .any(|(neg_idx, rej)| *rej && n_rejecting[neg_idx] == 1);
+        if !needed {
+            kept[clause_idx] = false;
+            for (neg_idx, rej) in rejected[clause_idx].iter().enumerate() {
+                if *rej {
+                    n_rejecting[neg_idx] -= 1;
+                }
+            }
+        }
+    }
+
+    clauses
+        .into_iter()
+        .zip(kept)
+        .filter(|(_, kept)| *kept)
+        .map(|(clause, _)| clause)
+        .collect()
+}
@@ -0,0 +1,64 @@
+use std::fmt;
+
+use crate::models::data_handling::row::Row;
+
+use super::{cnf_model::CnfModel, dnf_model::DnfModel};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RuleForm {
+    Cnf,
+    Dnf,
+}
+
+impl fmt::Display for RuleForm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleForm::Cnf => write!(f, "CNF"),
+            RuleForm::Dnf => write!(f, "DNF"),
+        }
+    }
+}
+
+// a learned model in either form, both classify the rows of the positive class
+#[derive(Clone, Debug, PartialEq)]
+pub enum RuleModel {
+    Cnf(CnfModel),
+    Dnf(DnfModel),
+}
+
+impl fmt::Display for RuleModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleModel::Cnf(model) => write!(f, "{}", model),
+            RuleModel::Dnf(model) => write!(f, "{}", model),
+        }
+    }
+}
+
+impl RuleModel {
+    pub fn form(&self) -> RuleForm {
+        match self {
+            RuleModel::Cnf(_) => RuleForm::Cnf,
+            RuleModel::Dnf(_) => RuleForm::Dnf,
+        }
+    }
+
+    // number of clauses of a CNF model, of terms of a DNF model
+    pub fn len(&self) -> usize {
+        match self {
+            RuleModel::Cnf(model) => model.len(),
+            RuleModel::Dnf(model) => model.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn predict(&self, row: &Row) -> bool {
+        match self {
+            RuleModel::Cnf(model) => model.predict(row),
+            RuleModel::Dnf(model) => model.predict(row),
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use rand::rngs::StdRng;
+
+use crate::models::{
+    ant_colony_optimization::{
+        aco::ACO, aco_parameters::ACOAlgorithm, edge_ac::EdgeAC, vertex_ac::VertexAC,
+    },
+    clique_solvers::{clique_cover::clique_cover, exact::ExactClique, greedy::Greedy},
+    config::Algorithm,
+    data_handling::dataset::Dataset,
+    graph::{rejectability::create_rejectability_graph, rejectability_graph::NoiseTolerance},
+};
+
+use super::{
+    cnf_model::CnfModel,
+    dnf_model::DnfModel,
+    pruning::prune_model,
+    rule_model::{RuleForm, RuleModel},
+};
+
+// options of the training pipeline that are not specific to the clique search
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrainingOptions {
+    pub threads: usize,
+    pub prune: bool,
+    pub tolerance: NoiseTolerance,
+    // forms of the models learned by a run, in order
+    pub forms: &'static [RuleForm],
+}
+
+impl TrainingOptions {
+    // options that change the learned model, as given on the command line
+    pub fn parameters(&self) -> Vec<(String, String)> {
+        vec![
+            ("prune".to_string(), self.prune.to_string()),
+            (
+                "positive-tolerance".to_string(),
+                self.tolerance.positives.to_string(),
+            ),
+            (
+                "negative-tolerance".to_string(),
+                self.tolerance.negatives.to_string(),
+            ),
+        ]
+    }
+}
+
+pub fn train_model(
+    rng: StdRng,
+    dataset: &Dataset,
+    algorithm: &Algorithm,
+    training: &TrainingOptions,
+    form: RuleForm,
+) -> RuleModel {
+    match form {
+        RuleForm::Cnf => RuleModel::Cnf(train_cnf_model(rng, dataset, algorithm, training)),
+        RuleForm::Dnf => RuleModel::Dnf(train_dnf_model(rng, dataset, algorithm, training)),
+    }
+}
+
+// learn a DNF model as the negation of the CNF model separating the negatives
+// from the positives, the tolerances are swapped with the classes so they
+// still bound the rejected positives and the accepted negatives
+pub fn train_dnf_model(
+    rng: StdRng,
+    dataset: &Dataset,
+    algorithm: &Algorithm,
+    training: &TrainingOptions,
+) -> DnfModel {
+    let swapped_training = TrainingOptions {
+        tolerance: NoiseTolerance {
+            positives: training.tolerance.negatives,
+            negatives: training.tolerance.positives,
+        },
+        ..*training
+    };
+    let model = train_cnf_model(rng, &dataset.swapped(), algorithm, &swapped_training);
+
+    DnfModel::negation_of(&model)
+}
+
+// learn a CNF model from the learning split of the dataset: build the
+// rejectability graph of the negatives and cover it with cliques
+pub fn train_cnf_model(
+    rng: StdRng,
+    dataset: &Dataset,
+    algorithm: &Algorithm,
+    training: &TrainingOptions,
+) -> CnfModel {
+    let model = cover_rejectability_graph(rng, dataset, algorithm, training);
+    if !training.prune {
+        return model;
+    }
+
+    let pruned = prune_model(&model, &dataset.learning_pos, &dataset.learning_neg);
+    println!(
+        "Pruned model: {} of {} clauses, {} of {} selectors",
+        pruned.len(),
+        model.len(),
+        pruned.n_selectors(),
+        model.n_selectors()
+    );
+    pruned
+}
+
+fn cover_rejectability_graph(
+    rng: StdRng,
+    dataset: &Dataset,
+    algorithm: &Algorithm,
+    training: &TrainingOptions,
+) -> CnfModel {
+    let graph = create_rejectability_graph(dataset, training.threads, training.tolerance);
+
+    println!(
+        "Rejectability graph created. Number of nodes: {}",
+        graph.n_vertex
+    );
+
+    match algorithm {
+        Algorithm::Ants(aco_algo, aco_parameters) => {
+            let mut aco_parameters = (**aco_parameters).clone();
+            aco_parameters.graph = graph;
+            aco_parameters.rand = rng;
+            aco_parameters.threads = training.threads;
+            match aco_algo {
+                ACOAlgorithm::VertexAC => {
+                    let mut vertex_ac = VertexAC::new(&aco_parameters);
+                    vertex_ac.clique_cover(&mut aco_parameters)
+                }
+                ACOAlgorithm::EdgeAC => {
+                    let mut edge_ac = EdgeAC::new(&aco_parameters);
+                    edge_ac.clique_cover(&mut aco_parameters)
+                }
+            }
+        }
+        Algorithm::Exact(time_limit) => {
+            let mut graph = graph;
+            let mut exact = ExactClique::new(*time_limit);
+            clique_cover(&mut graph, |graph| {
+                let clique = exact.find_clique(graph);
+                if !exact.last_search_optimal {
+                    println!("Time limit reached, the clique may not be maximum");
+                }
+                clique
+            })
+        }
+        Algorithm::Greedy(strategy) => {
+            let mut graph = graph;
+            let mut greedy = Greedy::new(*strategy, rng);
+            clique_cover(&mut graph, |graph| greedy.find_clique(graph))
+        }
+    }
+}
@@ -0,0 +1,577 @@
+// tests
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::models::{
+        ant_colony_optimization::{
+            aco::ACO,
+            aco_parameters::{ACOAlgorithm, ACOParameters},
+            vertex_ac::VertexAC,
+        },
+        classifier::{
+            cnf_model::CnfModel,
+            dnf_model::DnfModel,
+            model_file::{ModelFile, ModelFileError},
+            multi_class::{
+                train_multi_class_model, BinaryModel, MultiClassModel, MultiClassStrategy,
+            },
+            prediction::{predict_csv, PredictionSummary},
+            pruning::{drop_redundant_clauses, prune_model, prune_selectors},
+            rule_model::{RuleForm, RuleModel},
+            training::{train_cnf_model, train_dnf_model, train_model, TrainingOptions},
+        },
+        clause::{DisjunctiveClause, Selector},
+        clique_solvers::greedy::GreedyStrategy,
+        config::Algorithm,
+        data_handling::{
+            attribute_kind::AttributeKind,
+            attribute_values_set::{AttributeValuesSet, AttributeValuesSetList},
+            dataset::{Dataset, SplitMethod},
+            multi_class_dataset::MultiClassDataset,
+            row::Row,
+        },
+        graph::{rejectability::create_rejectability_graph, rejectability_graph::NoiseTolerance},
+    };
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn row(size: &str, color: &str) -> Row {
+        Row {
+            class: "".to_string(),
+            attributes: AttributeValuesSetList::from_vec(vec![
+                AttributeValuesSet::Cat("size".to_string(), HashSet::from([size.to_string()])),
+                AttributeValuesSet::Cat("color".to_string(), HashSet::from([color.to_string()])),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_cnf_model_predict() {
+        let model = CnfModel::new(vec![
+            DisjunctiveClause::new(vec![
+                Selector::new_eq("color".to_string(), "blue".to_string()),
+                Selector::new_eq("size".to_string(), "medium".to_string()),
+            ]),
+            DisjunctiveClause::new(vec![Selector::new_eq(
+                "size".to_string(),
+                "medium".to_string(),
+            )]),
+        ]);
+
+        assert_eq!(model.len(), 2);
+        assert!(model.predict(&row("medium", "red")));
+        assert!(model.predict(&row("medium", "blue")));
+        assert!(!model.predict(&row("small", "blue")));
+        assert!(!model.predict(&row("large", "green")));
+
+        assert!(CnfModel::new(vec![]).predict(&row("large", "green")));
+    }
+
+    #[test]
+    fn test_cnf_model_display() {
+        let model = CnfModel::new(vec![
+            DisjunctiveClause::new(vec![Selector::new_eq(
+                "color".to_string(),
+                "blue".to_string(),
+            )]),
+            DisjunctiveClause::new(vec![Selector::new_eq(
+                "size".to_string(),
+                "medium".to_string(),
+            )]),
+        ]);
+
+        assert_eq!(
+            format!("{}", model),
+            "( [color=blue] ) ∧ ( [size=medium] )".to_string()
+        );
+    }
+
+    #[test]
+    fn test_clique_cover_separates_learning_set() {
+        let rng = StdRng::seed_from_u64(1000);
+        let dataset = Dataset::new(rng.clone(), "datasets/test1.csv", "class", "yes", 80).unwrap();
+
+        let mut p = ACOParameters::new(5, 5, 1.0, 0.99, 6.0, 0.01, 1000);
+        p.graph = create_rejectability_graph(&dataset, 1, NoiseTolerance::default());
+        p.rand = rng;
+
+        let mut vertex_ac = VertexAC::new(&p);
+        let model = vertex_ac.clique_cover(&mut p);
+
+        assert!(!model.is_empty());
+        assert!(p.graph.available_vertex.is_empty());
+        for positive in &dataset.learning_pos {
+            assert!(model.predict(positive));
+        }
+        for negative in &dataset.learning_neg {
+            assert!(!model.predict(negative));
+        }
+    }
+
+    fn eq(attr: &str, value: &str) -> Selector {
+        Selector::new_eq(attr.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn test_prune_selectors() {
+        let positives = vec![
+            row("small", "red"),
+            row("large", "red"),
+            row("small", "blue"),
+        ];
+        let clause = DisjunctiveClause::new(vec![
+            eq("size", "medium"),
+            eq("color", "red"),
+            eq("size", "small"),
+            eq("color", "blue"),
+        ]);
+
+        // [size=medium] accepts no positive, then [color=blue] only accepts
+        // a positive that [size=small] accepts too
+        assert_eq!(
+            prune_selectors(&clause, &positives),
+            DisjunctiveClause::new(vec![eq("color", "red"), eq("size", "small")])
+        );
+    }
+
+    #[test]
+    fn test_drop_redundant_clauses() {
+        let negatives = vec![
+            row("small", "green"),
+            row("large", "green"),
+            row("large", "red"),
+        ];
+        let clauses = vec![
+            DisjunctiveClause::new(vec![eq("size", "medium")]),
+            DisjunctiveClause::new(vec![eq("color", "red")]),
+            DisjunctiveClause::new(vec![eq("size", "small")]),
+        ];
+
+        // the first clause rejects every negative, the others reject a subset of them
+        assert_eq!(
+            drop_redundant_clauses(clauses.clone(), &negatives),
+            vec![clauses[0].clone()]
+        );
+        assert_eq!(
+            drop_redundant_clauses(clauses[1..].to_vec(), &negatives),
+            clauses[1..].to_vec()
+        );
+    }
+
+    #[test]
+    fn test_dnf_model_is_the_negated_swapped_cnf() {
+        let rng = StdRng::seed_from_u64(1000);
+        let dataset =
+            Dataset::new(rng.clone(), "datasets/hayes_roth.csv", "class", "1", 80).unwrap();
+        let training = TrainingOptions {
+            threads: 2,
+            prune: true,
+            tolerance: NoiseTolerance::default(),
+            forms: &[RuleForm::Cnf, RuleForm::Dnf],
+        };
+        let algorithm = Algorithm::Greedy(GreedyStrategy::Degree);
+
+        let dnf = train_dnf_model(rng.clone(), &dataset, &algorithm, &training);
+        let swapped = train_cnf_model(rng.clone(), &dataset.swapped(), &algorithm, &training);
+        assert_eq!(dnf, DnfModel::negation_of(&swapped));
+        assert_eq!(
+            train_model(rng, &dataset, &algorithm, &training, RuleForm::Dnf),
+            RuleModel::Dnf(dnf.clone())
+        );
+
+        let rows = [
+            &dataset.learning_pos,
+            &dataset.learning_neg,
+            &dataset.testing_pos,
+            &dataset.testing_neg,
+        ];
+        for row in rows.into_iter().flatten() {
+            assert_eq!(dnf.predict(row), !swapped.predict(row));
+        }
+    }
+
+    #[test]
+    fn test_pruned_model_keeps_learning_separation() {
+        let rng = StdRng::seed_from_u64(1000);
+        let dataset =
+            Dataset::new(rng.clone(), "datasets/hayes_roth.csv", "class", "1", 80).unwrap();
+        let options = |prune: bool| TrainingOptions {
+            threads: 2,
+            prune,
+            tolerance: NoiseTolerance::default(),
+            forms: &[RuleForm::Cnf],
+        };
+        let algorithm = Algorithm::Ants(
+            ACOAlgorithm::VertexAC,
+            Box::new(ACOParameters::new(5, 5, 1.0, 0.99, 6.0, 0.01, 1000)),
+        );
+
+        let model = train_cnf_model(rng.clone(), &dataset, &algorithm, &options(false));
+        let pruned = train_cnf_model(rng, &dataset, &algorithm, &options(true));
+
+        assert_eq!(
+            pruned,
+            prune_model(&model, &dataset.learning_pos, &dataset.learning_neg)
+        );
+        assert!(pruned.len() < model.len());
+        assert!(pruned.n_selectors() < model.n_selectors());
+        // hayes-roth has contradictory rows, so only the examples the cover
+        // separates are kept separated
+        for positive in &dataset.learning_pos {
+            assert!(!model.predict(positive) || pruned.predict(positive));
+        }
+        for negative in &dataset.learning_neg {
+            assert!(model.predict(negative) || !pruned.predict(negative));
+        }
+    }
+
+    fn model_file() -> ModelFile {
+        ModelFile {
+            model: RuleModel::Cnf(CnfModel::new(vec![
+                DisjunctiveClause::new(vec![
+                    Selector::new_eq("color".to_string(), "dark\tblue\\".to_string()),
+                    Selector::new_leq("x".to_string(), 0.1 + 0.2),
+                    Selector::new_range("x".to_string(), -1e-300, 1.0 / 3.0),
+                ]),
+                DisjunctiveClause::new(vec![Selector::new_geq("x".to_string(), f64::NEG_INFINITY)]),
+                DisjunctiveClause::new(vec![]),
+            ])),
+            schema: vec![
+                ("color".to_string(), AttributeKind::Categorical),
+                ("x".to_string(), AttributeKind::Numeric),
+            ],
+            class_column: "class".to_string(),
+            positive_class: "yes\nno".to_string(),
+            parameters: vec![
+                ("algorithm".to_string(), "vertex-ac".to_string()),
+                ("alpha".to_string(), "1".to_string()),
+            ],
+            seed: u64::MAX,
+        }
+    }
+
+    #[test]
+    fn test_model_file_round_trip() {
+        let model_file = model_file();
+        let path = std::env::temp_dir().join(format!("oqat_model_{}.txt", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        model_file.save(path).unwrap();
+        let loaded = ModelFile::load(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(loaded, model_file);
+        assert_eq!(loaded.to_string(), model_file.to_string());
+
+        // the terms of a DNF model use the negated selectors
+        let RuleModel::Cnf(cnf) = &model_file.model else {
+            panic!("expected a CNF model");
+        };
+        let dnf_file = ModelFile {
+            model: RuleModel::Dnf(DnfModel::negation_of(cnf)),
+            ..model_file
+        };
+        let loaded = ModelFile::parse(&dnf_file.to_string()).unwrap();
+        assert_eq!(loaded, dnf_file);
+
+        // an empty DNF model stays one, it classifies every row as negative
+        let empty_file = ModelFile {
+            model: RuleModel::Dnf(DnfModel::default()),
+            ..dnf_file
+        };
+        let loaded = ModelFile::parse(&empty_file.to_string()).unwrap();
+        assert_eq!(loaded.model, RuleModel::Dnf(DnfModel::default()));
+    }
+
+    #[test]
+    fn test_model_file_version_1() {
+        let model_file = model_file();
+        let content = model_file
+            .to_string()
+            .replace("oqat-model 2\n", "oqat-cnf-model 1\n")
+            .replace("form\tcnf\n", "");
+        assert!(content.starts_with("oqat-cnf-model 1\nclass-column"));
+
+        assert_eq!(ModelFile::parse(&content).unwrap(), model_file);
+    }
+
+    #[test]
+    fn test_model_file_errors() {
+        let err = ModelFile::parse("cnf\n").unwrap_err();
+        assert_eq!(
+            err,
+            ModelFileError::Parse(1, "expected header 'oqat-model 2'".to_string())
+        );
+
+        let content = model_file().to_string().replace("leq\tx\t", "leq\tx\tabc");
+        assert!(matches!(
+            ModelFile::parse(&content),
+            Err(ModelFileError::Parse(_, reason)) if reason.starts_with("invalid threshold")
+        ));
+
+        let err = ModelFile::parse("oqat-model 2\neq\ta\tb\n").unwrap_err();
+        assert_eq!(
+            err,
+            ModelFileError::Parse(2, "selector outside of a clause".to_string())
+        );
+
+        let err = ModelFile::parse("oqat-model 2\nform\tdnf\nclause\n").unwrap_err();
+        assert_eq!(
+            err,
+            ModelFileError::Parse(3, "clause record in a DNF model".to_string())
+        );
+
+        let err = ModelFile::parse("oqat-model 2\nclass-column\tclass\n").unwrap_err();
+        assert_eq!(err, ModelFileError::Missing("positive-class".to_string()));
+        assert_eq!(err.to_string(), "Model file has no 'positive-class' record");
+
+        // version 1 files only hold CNF models
+        let err = ModelFile::parse("oqat-cnf-model 1\nform\tcnf\n").unwrap_err();
+        assert_eq!(
+            err,
+            ModelFileError::Parse(2, "unknown record 'form'".to_string())
+        );
+
+        assert!(matches!(
+            ModelFile::load("datasets/missing_model.txt"),
+            Err(ModelFileError::Io(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_predict_csv() {
+        let model_file = ModelFile {
+            model: RuleModel::Cnf(CnfModel::new(vec![DisjunctiveClause::new(vec![
+                Selector::new_eq("color".to_string(), "blue".to_string()),
+                Selector::new_leq("x".to_string(), 2.0),
+            ])])),
+            schema: vec![
+                ("color".to_string(), AttributeKind::Categorical),
+                ("x".to_string(), AttributeKind::Numeric),
+            ],
+            class_column: "class".to_string(),
+            positive_class: "yes".to_string(),
+            parameters: vec![],
+            seed: 1000,
+        };
+
+        // columns in another order, an extra column, an unseen color and text in a numeric column
+        let input = std::env::temp_dir().join(format!("oqat_predict_{}.csv", std::process::id()));
+        let input = input.to_str().unwrap();
+        std::fs::write(
+            input,
+            "id,x,color\n1,5,blue\n2,1.5,red\n3,7,purple\n4,n/a,green\n",
+        )
+        .unwrap();
+
+        let mut output = vec![];
+        let summary = predict_csv(&model_file, input, &mut output, "predicted", "no").unwrap();
+        assert_eq!(
+            summary,
+            PredictionSummary {
+                positive: 2,
+                negative: 2
+            }
+        );
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "id,x,color,predicted\n1,5,blue,yes\n2,1.5,red,yes\n3,7,purple,no\n4,n/a,green,no\n"
+        );
+
+        // the DNF model ( [color≠blue] ∧ [x>2] ): an unseen color differs from blue,
+        // but text in x or an empty color satisfies none of the negated selectors
+        let RuleModel::Cnf(cnf) = &model_file.model else {
+            panic!("expected a CNF model");
+        };
+        let dnf_file = ModelFile {
+            model: RuleModel::Dnf(DnfModel::negation_of(cnf)),
+            ..model_file.clone()
+        };
+        std::fs::write(
+            input,
+            "id,x,color\n1,5,blue\n2,1.5,red\n3,7,purple\n4,n/a,green\n5,9,\n",
+        )
+        .unwrap();
+        let mut output = vec![];
+        let summary = predict_csv(&dnf_file, input, &mut output, "predicted", "no").unwrap();
+        assert_eq!(
+            summary,
+            PredictionSummary {
+                positive: 1,
+                negative: 4
+            }
+        );
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "id,x,color,predicted\n1,5,blue,no\n2,1.5,red,no\n3,7,purple,yes\n4,n/a,green,no\n5,9,,no\n"
+        );
+
+        std::fs::write(input, "id,color\n1,blue\n").unwrap();
+        let err = predict_csv(&model_file, input, vec![], "predicted", "no").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("columns [x] of the model are missing"));
+
+        std::fs::write(input, "x,color,predicted\n1,blue,yes\n").unwrap();
+        let err = predict_csv(&model_file, input, vec![], "predicted", "no").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("column 'predicted' already exists"));
+
+        std::fs::remove_file(input).unwrap();
+    }
+
+    #[test]
+    fn test_multi_class_votes_and_ties() {
+        let accepts = |color: &str| {
+            RuleModel::Cnf(CnfModel::new(vec![DisjunctiveClause::new(vec![
+                Selector::new_eq("color".to_string(), color.to_string()),
+            ])]))
+        };
+        let classes = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let one_vs_rest = MultiClassModel {
+            classes: classes.clone(),
+            learning_counts: vec![2, 5, 5],
+            models: vec![
+                BinaryModel {
+                    positive: 0,
+                    negative: None,
+                    model: accepts("red"),
+                },
+                BinaryModel {
+                    positive: 1,
+                    negative: None,
+                    model: accepts("red"),
+                },
+                BinaryModel {
+                    positive: 2,
+                    negative: None,
+                    model: accepts("blue"),
+                },
+            ],
+        };
+
+        assert_eq!(one_vs_rest.votes(&row("small", "blue")), vec![0, 0, 1]);
+        assert_eq!(one_vs_rest.predict(&row("small", "blue")), "c");
+        // a conflict goes to the accepting class with the most learning rows
+        assert_eq!(one_vs_rest.predict(&row("small", "red")), "b");
+        // an abstention goes to the class with the most learning rows, then the first name
+        assert_eq!(one_vs_rest.predict(&row("small", "green")), "b");
+
+        let one_vs_one = MultiClassModel {
+            classes,
+            learning_counts: vec![1, 1, 1],
+            models: vec![
+                BinaryModel {
+                    positive: 0,
+                    negative: Some(1),
+                    model: accepts("red"),
+                },
+                BinaryModel {
+                    positive: 0,
+                    negative: Some(2),
+                    model: accepts("red"),
+                },
+                BinaryModel {
+                    positive: 1,
+                    negative: Some(2),
+                    model: accepts("blue"),
+                },
+            ],
+        };
+
+        assert_eq!(one_vs_one.votes(&row("small", "red")), vec![2, 0, 1]);
+        assert_eq!(one_vs_one.predict(&row("small", "red")), "a");
+        assert_eq!(one_vs_one.votes(&row("small", "blue")), vec![0, 2, 1]);
+        assert_eq!(one_vs_one.votes(&row("small", "green")), vec![0, 1, 2]);
+        assert_eq!(one_vs_one.predict(&row("small", "green")), "c");
+        // every class has as many learning rows, so the first tied name wins
+        let tied = MultiClassModel {
+            models: one_vs_one.models[..2].to_vec(),
+            ..one_vs_one.clone()
+        };
+        assert_eq!(tied.votes(&row("small", "green")), vec![0, 1, 1]);
+        assert_eq!(tied.predict(&row("small", "green")), "b");
+        let tied = MultiClassModel {
+            models: vec![],
+            ..one_vs_one
+        };
+        assert_eq!(tied.predict(&row("small", "green")), "a");
+    }
+
+    #[test]
+    fn test_train_multi_class_model() {
+        let rng = StdRng::seed_from_u64(1000);
+        let dataset = MultiClassDataset::with_split(
+            rng.clone(),
+            "datasets/hayes_roth.csv",
+            "class",
+            80,
+            SplitMethod::Stratified,
+        )
+        .unwrap();
+        let training = TrainingOptions {
+            threads: 2,
+            prune: true,
+            tolerance: NoiseTolerance::default(),
+            forms: &[RuleForm::Cnf],
+        };
+        let algorithm = Algorithm::Greedy(GreedyStrategy::Degree);
+
+        let one_vs_rest = train_multi_class_model(
+            rng.clone(),
+            &dataset,
+            MultiClassStrategy::OneVsRest,
+            &algorithm,
+            &training,
+            RuleForm::Cnf,
+        );
+        assert_eq!(one_vs_rest.learning_counts, dataset.learning_counts());
+        assert_eq!(
+            one_vs_rest
+                .models
+                .iter()
+                .map(|binary| (binary.positive, binary.negative))
+                .collect::<Vec<_>>(),
+            vec![(0, None), (1, None), (2, None)]
+        );
+        assert_eq!(
+            one_vs_rest.models[1].model,
+            train_model(
+                rng.clone(),
+                &dataset.one_vs_rest("2"),
+                &algorithm,
+                &training,
+                RuleForm::Cnf
+            )
+        );
+
+        let one_vs_one = train_multi_class_model(
+            rng,
+            &dataset,
+            MultiClassStrategy::OneVsOne,
+            &algorithm,
+            &training,
+            RuleForm::Dnf,
+        );
+        assert_eq!(
+            one_vs_one
+                .models
+                .iter()
+                .map(|binary| (binary.positive, binary.negative, binary.model.form()))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, Some(1), RuleForm::Dnf),
+                (0, Some(2), RuleForm::Dnf),
+                (1, Some(2), RuleForm::Dnf)
+            ]
+        );
+        for row in &dataset.learning {
+            assert!(dataset
+                .classes
+                .iter()
+                .any(|class| class == one_vs_one.predict(row)));
+        }
+    }
+}
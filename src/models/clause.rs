@@ -1,14 +1,35 @@
-use std::fmt;
+use std::{collections::HashSet, fmt};
 
+use ordered_float::OrderedFloat;
+
+use super::data_handling::{
+    attribute_values_set::{AttributeValuesSet, AttributeValuesSetList},
+    row::Row,
+};
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct DisjunctiveClause {
     pub selectors: Vec<Selector>,
 }
 
-#[derive(PartialEq)]
+// conjunction of selectors, the negation of a disjunctive clause
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConjunctiveTerm {
+    pub selectors: Vec<Selector>,
+}
+
+// Neq, Lt, Gt and Outside are the negations of Eq, Geq, Leq and Range,
+// they only appear in the terms of DNF models
+#[derive(Clone, Debug, PartialEq)]
 pub enum Selector {
     Eq(String, String),
     Leq(String, f64),
     Geq(String, f64),
+    Range(String, f64, f64),
+    Neq(String, String),
+    Lt(String, f64),
+    Gt(String, f64),
+    Outside(String, f64, f64),
 }
 
 impl fmt::Display for DisjunctiveClause {
@@ -24,12 +45,30 @@ impl fmt::Display for DisjunctiveClause {
     }
 }
 
+impl fmt::Display for ConjunctiveTerm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "( ")?;
+        for (idx, selector) in self.selectors.iter().enumerate() {
+            write!(f, "{}", selector)?;
+            if idx + 1 < self.selectors.len() {
+                write!(f, " ∧ ")?;
+            }
+        }
+        write!(f, " )")
+    }
+}
+
 impl fmt::Display for Selector {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Selector::Eq(attr, val) => write!(f, "[{}={}]", attr, val),
             Selector::Leq(attr, val) => write!(f, "[{}<={}]", attr, val),
             Selector::Geq(attr, val) => write!(f, "[{}>={}]", attr, val),
+            Selector::Range(attr, low, high) => write!(f, "[{}<={}<={}]", low, attr, high),
+            Selector::Neq(attr, val) => write!(f, "[{}≠{}]", attr, val),
+            Selector::Lt(attr, val) => write!(f, "[{}<{}]", attr, val),
+            Selector::Gt(attr, val) => write!(f, "[{}>{}]", attr, val),
+            Selector::Outside(attr, low, high) => write!(f, "[{}∉[{},{}]]", attr, low, high),
         }
     }
 }
@@ -38,6 +77,134 @@ impl DisjunctiveClause {
     pub fn new(selectors: Vec<Selector>) -> DisjunctiveClause {
         DisjunctiveClause { selectors }
     }
+
+    // a disjunction accepts a row when at least one of its selectors does
+    pub fn accepts(&self, row: &Row) -> bool {
+        self.selectors.iter().any(|selector| selector.accepts(row))
+    }
+
+    // term accepting exactly the rows the clause rejects
+    pub fn negation(&self) -> ConjunctiveTerm {
+        ConjunctiveTerm::new(self.selectors.iter().map(Selector::negation).collect())
+    }
+}
+
+impl ConjunctiveTerm {
+    pub fn new(selectors: Vec<Selector>) -> ConjunctiveTerm {
+        ConjunctiveTerm { selectors }
+    }
+
+    // a conjunction accepts a row when all of its selectors do
+    pub fn accepts(&self, row: &Row) -> bool {
+        self.selectors.iter().all(|selector| selector.accepts(row))
+    }
+}
+
+impl DisjunctiveClause {
+    // numeric values of the clause are grouped by the gap between the rejected values
+    // they fall in, and every group becomes a threshold selector placed halfway
+    // between the accepted and the rejected values, categorical values stay equalities
+    pub fn from_clause_and_rejected(
+        clause: &AttributeValuesSetList,
+        rejected: &AttributeValuesSetList,
+    ) -> DisjunctiveClause {
+        let mut selectors = vec![];
+
+        for (attr_idx, set) in clause.list.iter().enumerate() {
+            match (set, rejected.list.get(attr_idx)) {
+                (
+                    AttributeValuesSet::Num(attr, values),
+                    Some(AttributeValuesSet::Num(_, rejected_values)),
+                ) if !rejected_values.is_empty() => {
+                    let mut rejected_values = rejected_values.iter().copied().collect::<Vec<_>>();
+                    rejected_values.sort();
+                    selectors.extend(interval_selectors(attr, values, &rejected_values));
+                }
+                _ => {
+                    let single = AttributeValuesSetList::from_vec(vec![set.clone()]);
+                    selectors.extend(DisjunctiveClause::from(&single).selectors);
+                }
+            }
+        }
+
+        DisjunctiveClause::new(selectors)
+    }
+}
+
+fn interval_selectors(
+    attr: &str,
+    values: &HashSet<OrderedFloat<f64>>,
+    sorted_rejected: &[OrderedFloat<f64>],
+) -> Vec<Selector> {
+    let mut values = values.iter().copied().collect::<Vec<_>>();
+    values.sort();
+
+    // (gap index, lowest value, highest value) where gap i lies between
+    // the rejected values i - 1 and i
+    let mut groups: Vec<(usize, f64, f64)> = vec![];
+    for value in values {
+        let gap = sorted_rejected.partition_point(|rejected| *rejected < value);
+        if sorted_rejected.get(gap) == Some(&value) {
+            continue;
+        }
+
+        match groups.last_mut() {
+            Some((last_gap, _, high)) if *last_gap == gap => *high = value.0,
+            _ => groups.push((gap, value.0, value.0)),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(gap, low, high)| {
+            let below = if gap > 0 {
+                Some((sorted_rejected[gap - 1].0 + low) / 2.0)
+            } else {
+                None
+            };
+            let above = sorted_rejected
+                .get(gap)
+                .map(|rejected| (high + rejected.0) / 2.0);
+
+            match (below, above) {
+                (Some(below), Some(above)) => Selector::new_range(attr.to_string(), below, above),
+                (Some(below), None) => Selector::new_geq(attr.to_string(), below),
+                (None, Some(above)) => Selector::new_leq(attr.to_string(), above),
+                // the caller only asks for intervals when there are rejected values,
+                // so every gap has a rejected value on at least one side
+                (None, None) => unreachable!("interval selectors without rejected values"),
+            }
+        })
+        .collect()
+}
+
+// every value left in a clique clause becomes an equality selector
+impl From<&AttributeValuesSetList> for DisjunctiveClause {
+    fn from(clause: &AttributeValuesSetList) -> Self {
+        let mut selectors = vec![];
+
+        for set in &clause.list {
+            match set {
+                AttributeValuesSet::Num(attr, values) => {
+                    let mut values = values.iter().copied().collect::<Vec<_>>();
+                    values.sort();
+                    for value in values {
+                        selectors.push(Selector::new_eq(attr.clone(), value.to_string()));
+                    }
+                }
+                AttributeValuesSet::Cat(attr, values) => {
+                    let mut values = values.iter().cloned().collect::<Vec<_>>();
+                    values.sort();
+                    for value in values {
+                        selectors.push(Selector::new_eq(attr.clone(), value));
+                    }
+                }
+                AttributeValuesSet::Empty => continue,
+            }
+        }
+
+        DisjunctiveClause::new(selectors)
+    }
 }
 
 impl Selector {
@@ -52,6 +219,86 @@ impl Selector {
     pub fn new_geq(attr: String, value: f64) -> Selector {
         Selector::Geq(attr, value)
     }
+
+    pub fn new_range(attr: String, low: f64, high: f64) -> Selector {
+        Selector::Range(attr, low, high)
+    }
+
+    // selector accepting exactly the rows this one rejects
+    pub fn negation(&self) -> Selector {
+        match self {
+            Selector::Eq(attr, value) => Selector::Neq(attr.clone(), value.clone()),
+            Selector::Leq(attr, value) => Selector::Gt(attr.clone(), *value),
+            Selector::Geq(attr, value) => Selector::Lt(attr.clone(), *value),
+            Selector::Range(attr, low, high) => Selector::Outside(attr.clone(), *low, *high),
+            Selector::Neq(attr, value) => Selector::Eq(attr.clone(), value.clone()),
+            Selector::Lt(attr, value) => Selector::Geq(attr.clone(), *value),
+            Selector::Gt(attr, value) => Selector::Leq(attr.clone(), *value),
+            Selector::Outside(attr, low, high) => Selector::Range(attr.clone(), *low, *high),
+        }
+    }
+
+    // check if the value of the row for the selector's attribute satisfies it
+    pub fn accepts(&self, row: &Row) -> bool {
+        match self {
+            Selector::Eq(attr, value) => match row.get_attribute(attr) {
+                Some(AttributeValuesSet::Cat(_, row_values)) => row_values.contains(value),
+                Some(AttributeValuesSet::Num(_, row_values)) => match value.parse::<f64>() {
+                    Ok(num) => row_values.contains(&OrderedFloat(num)),
+                    Err(_) => false,
+                },
+                _ => false,
+            },
+            Selector::Leq(attr, value) => match row.get_attribute(attr) {
+                Some(AttributeValuesSet::Num(_, row_values)) => {
+                    row_values.iter().any(|row_value| row_value.0 <= *value)
+                }
+                _ => false,
+            },
+            Selector::Geq(attr, value) => match row.get_attribute(attr) {
+                Some(AttributeValuesSet::Num(_, row_values)) => {
+                    row_values.iter().any(|row_value| row_value.0 >= *value)
+                }
+                _ => false,
+            },
+            Selector::Range(attr, low, high) => match row.get_attribute(attr) {
+                Some(AttributeValuesSet::Num(_, row_values)) => row_values
+                    .iter()
+                    .any(|row_value| *low <= row_value.0 && row_value.0 <= *high),
+                _ => false,
+            },
+            // a missing attribute, an empty cell or text in a numeric column satisfies
+            // no selector, negated or not, while an unseen category differs from value
+            Selector::Neq(attr, value) => match row.get_attribute(attr) {
+                Some(AttributeValuesSet::Cat(_, row_values)) => row_values
+                    .iter()
+                    .any(|row_value| !row_value.is_empty() && row_value != value),
+                Some(AttributeValuesSet::Num(_, row_values)) => match value.parse::<f64>() {
+                    Ok(num) => row_values.iter().any(|row_value| row_value.0 != num),
+                    Err(_) => !row_values.is_empty(),
+                },
+                _ => false,
+            },
+            Selector::Lt(attr, value) => match row.get_attribute(attr) {
+                Some(AttributeValuesSet::Num(_, row_values)) => {
+                    row_values.iter().any(|row_value| row_value.0 < *value)
+                }
+                _ => false,
+            },
+            Selector::Gt(attr, value) => match row.get_attribute(attr) {
+                Some(AttributeValuesSet::Num(_, row_values)) => {
+                    row_values.iter().any(|row_value| row_value.0 > *value)
+                }
+                _ => false,
+            },
+            Selector::Outside(attr, low, high) => match row.get_attribute(attr) {
+                Some(AttributeValuesSet::Num(_, row_values)) => row_values
+                    .iter()
+                    .any(|row_value| row_value.0 < *low || *high < row_value.0),
+                _ => false,
+            },
+        }
+    }
 }
 
 // tests
@@ -65,12 +312,141 @@ mod tests {
             Selector::new_eq("a".to_string(), "b".to_string()),
             Selector::new_leq("c".to_string(), 1.0),
             Selector::new_geq("d".to_string(), 2.0),
+            Selector::new_range("e".to_string(), 1.5, 3.0),
         ];
         let clause = DisjunctiveClause::new(selectors);
 
         assert_eq!(
             format!("{}", clause),
-            "( [a=b] ∨ [c<=1] ∨ [d>=2] )".to_string()
+            "( [a=b] ∨ [c<=1] ∨ [d>=2] ∨ [1.5<=e<=3] )".to_string()
+        );
+    }
+
+    #[test]
+    fn test_accepts() {
+        let row = Row {
+            class: "yes".to_string(),
+            attributes: AttributeValuesSetList::from_vec(vec![
+                AttributeValuesSet::Cat("a".to_string(), HashSet::from(["b".to_string()])),
+                AttributeValuesSet::Num("c".to_string(), HashSet::from([OrderedFloat(1.5)])),
+            ]),
+        };
+
+        assert!(Selector::new_eq("a".to_string(), "b".to_string()).accepts(&row));
+        assert!(!Selector::new_eq("a".to_string(), "x".to_string()).accepts(&row));
+        assert!(Selector::new_eq("c".to_string(), "1.5".to_string()).accepts(&row));
+        assert!(Selector::new_leq("c".to_string(), 1.5).accepts(&row));
+        assert!(!Selector::new_geq("c".to_string(), 2.0).accepts(&row));
+        assert!(!Selector::new_eq("z".to_string(), "b".to_string()).accepts(&row));
+
+        let clause = DisjunctiveClause::new(vec![
+            Selector::new_eq("a".to_string(), "x".to_string()),
+            Selector::new_geq("c".to_string(), 1.0),
+        ]);
+        assert!(clause.accepts(&row));
+
+        let clause = DisjunctiveClause::new(vec![]);
+        assert!(!clause.accepts(&row));
+    }
+
+    #[test]
+    fn test_negation() {
+        let row = |x: f64| Row {
+            class: "".to_string(),
+            attributes: AttributeValuesSetList::from_vec(vec![
+                AttributeValuesSet::Cat("a".to_string(), HashSet::from(["b".to_string()])),
+                AttributeValuesSet::Num("x".to_string(), HashSet::from([OrderedFloat(x)])),
+            ]),
+        };
+        let clause = DisjunctiveClause::new(vec![
+            Selector::new_eq("a".to_string(), "z".to_string()),
+            Selector::new_leq("x".to_string(), 1.0),
+            Selector::new_range("x".to_string(), 3.0, 4.0),
+            Selector::new_geq("x".to_string(), 8.0),
+        ]);
+        let term = clause.negation();
+
+        assert_eq!(
+            format!("{}", term),
+            "( [a≠z] ∧ [x>1] ∧ [x∉[3,4]] ∧ [x<8] )".to_string()
+        );
+        for x in [0.5, 1.0, 2.0, 3.5, 5.0, 8.0, 9.0] {
+            assert_eq!(term.accepts(&row(x)), !clause.accepts(&row(x)));
+        }
+        for selector in &clause.selectors {
+            assert_eq!(&selector.negation().negation(), selector);
+        }
+
+        // an empty clause rejects every row, so its negation accepts them all
+        assert!(DisjunctiveClause::new(vec![]).negation().accepts(&row(0.0)));
+        // an empty cell or text in a numeric column satisfies no negated selector either
+        let unreadable = Row {
+            class: "".to_string(),
+            attributes: AttributeValuesSetList::from_vec(vec![
+                AttributeValuesSet::Cat("a".to_string(), HashSet::from(["".to_string()])),
+                AttributeValuesSet::Cat("x".to_string(), HashSet::from(["n/a".to_string()])),
+            ]),
+        };
+        for selector in &term.selectors {
+            assert!(!selector.accepts(&unreadable));
+            assert!(!selector.negation().accepts(&unreadable));
+        }
+        assert!(!Selector::Neq("z".to_string(), "b".to_string()).accepts(&row(0.0)));
+    }
+
+    #[test]
+    fn test_from_attribute_values_set_list() {
+        let list = AttributeValuesSetList::from_vec(vec![
+            AttributeValuesSet::Cat(
+                "a".to_string(),
+                HashSet::from(["y".to_string(), "x".to_string()]),
+            ),
+            AttributeValuesSet::Num("c".to_string(), HashSet::from([OrderedFloat(2.0)])),
+            AttributeValuesSet::Cat("d".to_string(), HashSet::new()),
+        ]);
+        let clause = DisjunctiveClause::from(&list);
+
+        assert_eq!(
+            format!("{}", clause),
+            "( [a=x] ∨ [a=y] ∨ [c=2] )".to_string()
         );
     }
+
+    #[test]
+    fn test_from_clause_and_rejected() {
+        let floats = |values: &[f64]| values.iter().map(|v| OrderedFloat(*v)).collect();
+        let clause = AttributeValuesSetList::from_vec(vec![
+            AttributeValuesSet::Num("x".to_string(), floats(&[1.0, 2.0, 5.0, 9.0, 10.0])),
+            AttributeValuesSet::Cat("a".to_string(), HashSet::from(["y".to_string()])),
+        ]);
+        let rejected = AttributeValuesSetList::from_vec(vec![
+            AttributeValuesSet::Num("x".to_string(), floats(&[3.0, 7.0])),
+            AttributeValuesSet::Cat("a".to_string(), HashSet::from(["z".to_string()])),
+        ]);
+        let clause = DisjunctiveClause::from_clause_and_rejected(&clause, &rejected);
+
+        assert_eq!(
+            clause.selectors,
+            vec![
+                Selector::new_leq("x".to_string(), 2.5),
+                Selector::new_range("x".to_string(), 4.0, 6.0),
+                Selector::new_geq("x".to_string(), 8.0),
+                Selector::new_eq("a".to_string(), "y".to_string()),
+            ]
+        );
+
+        let unseen = |x: f64| Row {
+            class: "".to_string(),
+            attributes: AttributeValuesSetList::from_vec(vec![
+                AttributeValuesSet::Num("x".to_string(), HashSet::from([OrderedFloat(x)])),
+                AttributeValuesSet::Cat("a".to_string(), HashSet::from(["z".to_string()])),
+            ]),
+        };
+        assert!(clause.accepts(&unseen(0.3)));
+        assert!(clause.accepts(&unseen(4.3)));
+        assert!(clause.accepts(&unseen(12.0)));
+        assert!(!clause.accepts(&unseen(3.0)));
+        assert!(!clause.accepts(&unseen(7.0)));
+        assert!(!clause.accepts(&unseen(6.5)));
+    }
 }
@@ -0,0 +1,4 @@
+pub mod clique_cover;
+pub mod exact;
+pub mod greedy;
+pub mod unit_tests;
@@ -0,0 +1,19 @@
+use std::collections::HashSet;
+
+use crate::models::{classifier::cnf_model::CnfModel, graph::rejectability_graph::Graph};
+
+// cover the rejectability graph with the cliques returned by `find_clique`,
+// every clique becomes a clause of the model
+pub fn clique_cover<F>(graph: &mut Graph, mut find_clique: F) -> CnfModel
+where
+    F: FnMut(&mut Graph) -> HashSet<usize>,
+{
+    let mut model = CnfModel::new(vec![]);
+
+    while !graph.is_covered() {
+        let best_clique = find_clique(graph);
+        model.add_clause(graph.cover_clique(best_clique));
+    }
+
+    model
+}
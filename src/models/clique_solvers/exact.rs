@@ -0,0 +1,124 @@
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+use crate::models::graph::{incremental_clique::IncrementalClique, rejectability_graph::Graph};
+
+// Branch and bound search of the largest complete clique among the available
+// vertices, following the MCQ algorithm of Tomita and Seki: candidates are
+// greedily coloured and a branch is pruned when the current clique plus the
+// number of colours left cannot beat the best clique found so far.
+//
+// Completeness (the clique clause accepts every positive) can only be lost
+// when vertices are added, so an incomplete clique is never extended.
+pub struct ExactClique {
+    // limit of every single clique search, the best clique found so far is
+    // returned when it is reached
+    time_limit: Option<Duration>,
+    deadline: Option<Instant>,
+    // false when the last search was stopped by the time limit
+    pub last_search_optimal: bool,
+    best: HashSet<usize>,
+}
+
+impl ExactClique {
+    pub fn new(time_limit: Option<Duration>) -> ExactClique {
+        ExactClique {
+            time_limit,
+            deadline: None,
+            last_search_optimal: true,
+            best: HashSet::new(),
+        }
+    }
+
+    pub fn find_clique(&mut self, graph: &Graph) -> HashSet<usize> {
+        self.deadline = self.time_limit.map(|limit| Instant::now() + limit);
+        self.last_search_optimal = true;
+
+        let mut candidates = graph.available_vertex.iter().copied().collect::<Vec<_>>();
+        candidates.sort_unstable();
+
+        // any single vertex is a valid clique, as for the ants
+        self.best = candidates
+            .first()
+            .map_or(HashSet::new(), |v| HashSet::from([*v]));
+        self.expand(graph, None, candidates);
+
+        self.best.clone()
+    }
+
+    // every branch extends its own copy of the clique, which is none at the root
+    fn expand(
+        &mut self,
+        graph: &Graph,
+        clique: Option<&IncrementalClique>,
+        candidates: Vec<usize>,
+    ) {
+        let (mut ordered, colors) = color_sort(graph, candidates);
+        let clique_len = clique.map_or(0, |clique| clique.clique.len());
+
+        while let Some(vertex) = ordered.pop() {
+            let color = colors[ordered.len()];
+            if clique_len + color <= self.best.len() {
+                return;
+            }
+            if self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                self.last_search_optimal = false;
+                return;
+            }
+
+            let extended = match clique {
+                None => Some(IncrementalClique::new(graph, vertex)),
+                Some(clique) => {
+                    let mut extended = clique.clone();
+                    extended.try_add(graph, vertex).then_some(extended)
+                }
+            };
+            if let Some(extended) = extended {
+                if extended.clique.len() > self.best.len() {
+                    self.best = extended.clique.clone();
+                }
+
+                let new_candidates = ordered
+                    .iter()
+                    .copied()
+                    .filter(|other| graph.is_edge(vertex, *other))
+                    .collect::<Vec<_>>();
+                if !new_candidates.is_empty() {
+                    self.expand(graph, Some(&extended), new_candidates);
+                }
+            }
+        }
+    }
+}
+
+// greedy colouring of the candidates, returns them sorted by colour with the
+// colour of each one, the colour count of a prefix bounds the clique inside it
+fn color_sort(graph: &Graph, candidates: Vec<usize>) -> (Vec<usize>, Vec<usize>) {
+    let mut color_classes: Vec<Vec<usize>> = vec![];
+
+    for vertex in candidates {
+        match color_classes
+            .iter_mut()
+            .find(|class| class.iter().all(|other| !graph.is_edge(vertex, *other)))
+        {
+            Some(class) => class.push(vertex),
+            None => color_classes.push(vec![vertex]),
+        }
+    }
+
+    let mut ordered = vec![];
+    let mut colors = vec![];
+    for (color, class) in color_classes.into_iter().enumerate() {
+        for vertex in class {
+            ordered.push(vertex);
+            colors.push(color + 1);
+        }
+    }
+
+    (ordered, colors)
+}
@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+
+use rand::{rngs::StdRng, seq::SliceRandom};
+
+use crate::models::graph::{incremental_clique::IncrementalClique, rejectability_graph::Graph};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GreedyStrategy {
+    // add the candidate with the most neighbors among the other candidates
+    Degree,
+    // add the candidate that keeps the most values in the clique clause
+    ClauseLoss,
+    // add random candidates, keep the largest clique of all the restarts
+    Random(usize),
+}
+
+// Deterministic (or seeded) baselines for the clique search: a clique is grown
+// from one vertex, adding one candidate at a time as long as the clique stays
+// complete, until no neighbor of every clique vertex is left.
+pub struct Greedy {
+    strategy: GreedyStrategy,
+    rng: StdRng,
+}
+
+impl Greedy {
+    pub fn new(strategy: GreedyStrategy, rng: StdRng) -> Greedy {
+        Greedy { strategy, rng }
+    }
+
+    pub fn find_clique(&mut self, graph: &Graph) -> HashSet<usize> {
+        let mut available = graph
+            .available_vertex
+            .iter()
+            .copied()
+            .collect::<Vec<usize>>();
+        available.sort_unstable();
+
+        match self.strategy {
+            GreedyStrategy::Degree | GreedyStrategy::ClauseLoss => {
+                // start from the available vertex with the most available neighbors
+                let initial_vertex = *available
+                    .iter()
+                    .rev()
+                    .max_by_key(|v| graph.get_neighbor_candidates(**v).len())
+                    .unwrap();
+                self.grow_clique(graph, initial_vertex)
+            }
+            GreedyStrategy::Random(restarts) => {
+                let mut best: HashSet<usize> = HashSet::new();
+                for _ in 0..restarts.max(1) {
+                    let initial_vertex = *available.choose(&mut self.rng).unwrap();
+                    let clique = self.grow_clique(graph, initial_vertex);
+                    if clique.len() > best.len() {
+                        best = clique;
+                    }
+                }
+                best
+            }
+        }
+    }
+
+    fn grow_clique(&mut self, graph: &Graph, initial_vertex: usize) -> HashSet<usize> {
+        let mut clique = IncrementalClique::new(graph, initial_vertex);
+        let mut candidates = graph.get_neighbor_candidates(initial_vertex);
+
+        while !candidates.is_empty() {
+            let new_v = self.choose_candidate(graph, &candidates, &clique);
+
+            if clique.try_add(graph, new_v) {
+                let new_v_candidates = graph.get_neighbor_candidates(new_v);
+                candidates = candidates
+                    .intersection(&new_v_candidates)
+                    .copied()
+                    .collect();
+            } else {
+                candidates.remove(&new_v);
+            }
+        }
+
+        clique.clique
+    }
+
+    // ties are broken by the smallest vertex so the greedy choices are deterministic
+    fn choose_candidate(
+        &mut self,
+        graph: &Graph,
+        candidates: &HashSet<usize>,
+        clique: &IncrementalClique,
+    ) -> usize {
+        let mut sorted_candidates = candidates.iter().copied().collect::<Vec<usize>>();
+        sorted_candidates.sort_unstable();
+
+        match self.strategy {
+            GreedyStrategy::Degree => *sorted_candidates
+                .iter()
+                .rev()
+                .max_by_key(|v| {
+                    candidates
+                        .iter()
+                        .filter(|other| graph.is_edge(**v, **other))
+                        .count()
+                })
+                .unwrap(),
+            GreedyStrategy::ClauseLoss => *sorted_candidates
+                .iter()
+                .rev()
+                .max_by_key(|v| clique.clause_with(graph, **v).n_values())
+                .unwrap(),
+            GreedyStrategy::Random(_) => *sorted_candidates.choose(&mut self.rng).unwrap(),
+        }
+    }
+}
@@ -0,0 +1,236 @@
+// tests
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, time::Duration};
+
+    use crate::models::{
+        clique_solvers::{
+            clique_cover::clique_cover,
+            exact::ExactClique,
+            greedy::{Greedy, GreedyStrategy},
+        },
+        data_handling::{
+            attribute_values_set::{AttributeValuesSet, AttributeValuesSetList},
+            dataset::Dataset,
+        },
+        graph::{
+            rejectability::create_rejectability_graph,
+            rejectability_graph::{Graph, NoiseTolerance},
+        },
+    };
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn test2_graph() -> (Dataset, Graph) {
+        let rng = StdRng::seed_from_u64(1000);
+        let dataset =
+            Dataset::new(rng.clone(), "datasets/test2.csv", "class", "perfect", 80).unwrap();
+        let graph = create_rejectability_graph(&dataset, 1, NoiseTolerance::default());
+        (dataset, graph)
+    }
+
+    // size of the largest complete clique, checking every subset of the vertices
+    fn brute_force_max_clique(graph: &Graph) -> usize {
+        let mut best = 1;
+        for mask in 1usize..(1 << graph.n_vertex) {
+            let clique = (0..graph.n_vertex)
+                .filter(|v| mask & (1 << v) != 0)
+                .collect::<Vec<usize>>();
+            if clique.len() <= best {
+                continue;
+            }
+            let is_clique = clique
+                .iter()
+                .all(|u| clique.iter().all(|v| u == v || graph.is_edge(*u, *v)));
+            if is_clique && graph.clique_is_complete(&clique.iter().copied().collect()) {
+                best = clique.len();
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn test_exact_clique_is_maximum() {
+        let (_, graph) = test2_graph();
+
+        let mut exact = ExactClique::new(None);
+        let clique = exact.find_clique(&graph);
+
+        assert!(exact.last_search_optimal);
+        assert!(graph.clique_is_complete(&clique));
+        for u in &clique {
+            for v in &clique {
+                assert!(u == v || graph.is_edge(*u, *v));
+            }
+        }
+        assert_eq!(clique.len(), brute_force_max_clique(&graph));
+    }
+
+    #[test]
+    fn test_exact_clique_only_uses_available_vertices() {
+        let clause = AttributeValuesSetList::from_vec(vec![AttributeValuesSet::Cat(
+            "a".to_string(),
+            HashSet::new(),
+        )]);
+        let mut graph = Graph::new(5, vec![], vec![], vec![]);
+        for (u, v) in [(0, 1), (0, 2), (1, 2), (3, 4)] {
+            graph.add_edge(u, v, &clause);
+        }
+
+        let mut exact = ExactClique::new(None);
+        assert_eq!(exact.find_clique(&graph), HashSet::from([0, 1, 2]));
+
+        graph.remove_vertex_set_from_available(&HashSet::from([0]));
+        assert_eq!(exact.find_clique(&graph).len(), 2);
+    }
+
+    #[test]
+    fn test_exact_clique_time_limit() {
+        let (_, graph) = test2_graph();
+
+        let mut exact = ExactClique::new(Some(Duration::ZERO));
+        let clique = exact.find_clique(&graph);
+
+        assert!(!exact.last_search_optimal);
+        assert_eq!(clique.len(), 1);
+    }
+
+    #[test]
+    fn test_exact_clique_cover() {
+        let (dataset, mut graph) = test2_graph();
+
+        let mut exact = ExactClique::new(None);
+        let model = clique_cover(&mut graph, |graph| exact.find_clique(graph));
+
+        assert!(graph.available_vertex.is_empty());
+        for positive in &dataset.learning_pos {
+            assert!(model.predict(positive));
+        }
+        for negative in &dataset.learning_neg {
+            assert!(!model.predict(negative));
+        }
+    }
+
+    fn assert_is_valid_clique(graph: &Graph, clique: &HashSet<usize>) {
+        assert!(!clique.is_empty());
+        assert!(graph.clique_is_complete(clique) || clique.len() == 1);
+        for u in clique {
+            assert!(graph.available_vertex.contains(u));
+            for v in clique {
+                assert!(u == v || graph.is_edge(*u, *v));
+            }
+        }
+    }
+
+    #[test]
+    fn test_greedy_cliques_are_valid_and_deterministic() {
+        let (_, graph) = test2_graph();
+
+        for strategy in [
+            GreedyStrategy::Degree,
+            GreedyStrategy::ClauseLoss,
+            GreedyStrategy::Random(5),
+        ] {
+            let mut first = Greedy::new(strategy, StdRng::seed_from_u64(7));
+            let mut second = Greedy::new(strategy, StdRng::seed_from_u64(7));
+            let clique = first.find_clique(&graph);
+
+            assert_is_valid_clique(&graph, &clique);
+            assert_eq!(clique, second.find_clique(&graph));
+        }
+    }
+
+    #[test]
+    fn test_random_greedy_restarts() {
+        let (_, graph) = test2_graph();
+
+        let single =
+            Greedy::new(GreedyStrategy::Random(1), StdRng::seed_from_u64(7)).find_clique(&graph);
+        let restarted =
+            Greedy::new(GreedyStrategy::Random(20), StdRng::seed_from_u64(7)).find_clique(&graph);
+
+        // the first restart draws the same clique as the single try
+        assert!(restarted.len() >= single.len());
+    }
+
+    #[test]
+    fn test_greedy_clique_cover() {
+        for strategy in [
+            GreedyStrategy::Degree,
+            GreedyStrategy::ClauseLoss,
+            GreedyStrategy::Random(3),
+        ] {
+            let (dataset, mut graph) = test2_graph();
+
+            let mut greedy = Greedy::new(strategy, StdRng::seed_from_u64(7));
+            let model = clique_cover(&mut graph, |graph| greedy.find_clique(graph));
+
+            assert!(graph.available_vertex.is_empty());
+            for positive in &dataset.learning_pos {
+                assert!(model.predict(positive));
+            }
+            for negative in &dataset.learning_neg {
+                assert!(!model.predict(negative));
+            }
+        }
+    }
+
+    #[test]
+    fn test_tolerant_clique_cover() {
+        let rng = StdRng::seed_from_u64(1000);
+        let dataset =
+            Dataset::new(rng.clone(), "datasets/hayes_roth.csv", "class", "2", 80).unwrap();
+        // with this tolerance for every single clause, the model would miss
+        // more than twice the tolerated positives
+        let tolerance = NoiseTolerance {
+            positives: 0.15,
+            negatives: 0.1,
+        };
+        let graph = create_rejectability_graph(&dataset, 1, tolerance);
+        let max_missed_positives = graph.max_missed_positives;
+        let max_unrejected_negatives = graph.max_unrejected_negatives;
+        assert!(max_missed_positives > 0 && max_unrejected_negatives > 0);
+
+        let mut covers = vec![];
+        for strategy in [
+            GreedyStrategy::Degree,
+            GreedyStrategy::ClauseLoss,
+            GreedyStrategy::Random(3),
+        ] {
+            let mut graph = graph.clone();
+            let mut greedy = Greedy::new(strategy, StdRng::seed_from_u64(7));
+            let model = clique_cover(&mut graph, |graph| greedy.find_clique(graph));
+            covers.push((graph, model));
+        }
+        let mut exact_graph = graph.clone();
+        let mut exact = ExactClique::new(Some(Duration::from_millis(200)));
+        let model = clique_cover(&mut exact_graph, |graph| exact.find_clique(graph));
+        covers.push((exact_graph, model));
+
+        for (graph, model) in covers {
+            // the cover stops as soon as enough negatives are rejected
+            assert!(graph.available_vertex.len() <= max_unrejected_negatives);
+            let unrejected = dataset
+                .learning_neg
+                .iter()
+                .filter(|negative| model.predict(negative))
+                .count();
+            assert!(unrejected <= max_unrejected_negatives);
+
+            // the clauses together miss at most the tolerated positives, besides
+            // the ones equal to a negative, which no clause can separate
+            let missed = dataset
+                .learning_pos
+                .iter()
+                .filter(|positive| !model.predict(positive))
+                .filter(|positive| {
+                    !dataset
+                        .learning_neg
+                        .iter()
+                        .any(|negative| negative.attributes == positive.attributes)
+                })
+                .count();
+            assert!(missed <= max_missed_positives);
+        }
+    }
+}
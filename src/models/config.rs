@@ -1,72 +1,1277 @@
 // Enum with the different options to run
 
-use super::ant_colony_optimization::aco_parameters::{ACOAlgorithm, ACOParameters};
+use std::{collections::HashMap, str::FromStr, thread, time::Duration};
+
+use super::{
+    ant_colony_optimization::aco_parameters::{
+        ACOAlgorithm, ACOHeuristic, ACOParameters, PheromoneUpdate,
+    },
+    classifier::{
+        multi_class::MultiClassStrategy, rule_model::RuleForm, training::TrainingOptions,
+    },
+    clique_solvers::greedy::GreedyStrategy,
+    data_handling::dataset::SplitMethod,
+    graph::rejectability_graph::NoiseTolerance,
+};
+
+pub const USAGE: &str =
+    "OQAT: learn CNF or DNF rules with ant colony optimization over a rejectability graph
+
+Usage:
+    oqat help
+    oqat run --dataset <path> --positive-class <value> [options]
+    oqat cross-validate --dataset <path> --positive-class <value> [options]
+    oqat multi-class --dataset <path> [options]
+    oqat predict --model <path> --input <path> [options]
+
+Flags can be given in any order, as `--flag value` or `--flag=value`.
+
+Dataset options:
+    --dataset <path>            CSV file with the examples (required)
+    --positive-class <value>    value of the class column taken as positive (required)
+    --class-column <name>       name of the class column [default: class]
+    --seed <n|entropy>          seed of the random generator, `entropy` draws a
+                                fresh one from the OS [default: 1000]
+
+Training options:
+    --threads <n>               threads building the rejectability graph and the
+                                ants of a generation, the result does not depend
+                                on it [default: all cores]
+    --prune <true|false>        remove the selectors and clauses the learning
+                                examples do not need [default: false]
+    --positive-tolerance <0-1>  fraction of the learning positives the model may
+                                reject over all its clauses, positives equal to
+                                a rejected negative are missed on top [default: 0]
+    --negative-tolerance <0-1>  fraction of the learning negatives the model may
+                                leave unrejected, the cover stops once no more
+                                are left [default: 0]
+    --form <name>               cnf | dnf | both, a DNF model is learned as the
+                                negated CNF of the swapped classes [default: cnf]
+
+Run options:
+    --learning-frac <1-100>     percentage of rows used for learning [default: 80]
+    --split <method>            random | stratified, stratified keeps the class
+                                ratio in both sets [default: random]
+    --testing-dataset <path>    CSV file with the same columns used as testing set,
+                                the whole --dataset is then used for learning
+    --save-model <path>         write the learned CNF model to a file
+    --save-dnf-model <path>     write the learned DNF model to a file
+
+Cross-validation options:
+    --folds <n>                 number of stratified folds [default: 5]
+
+Multi-class options:
+    --strategy <name>           one-vs-rest | one-vs-one, one model per class or
+                                per pair of classes [default: one-vs-rest]
+    --learning-frac, --split    as for run
+    Every model votes for a class and the class with the most votes is predicted.
+    Ties, and rows no one-vs-rest model accepts, go to the tied class with the
+    most learning rows, then to the first one by name. --positive-class is not
+    used, all the dataset and training options are.
+
+Predict options:
+    --model <path>              model file written by `run --save-model` (required)
+    --input <path>              CSV file with the rows to classify (required)
+    --output <path>             CSV file for the rows and their predicted class
+                                [default: stdout]
+    --predicted-column <name>   name of the appended column [default: predicted]
+    --negative-label <value>    label of rows that are not predicted positive
+                                [default: other]
+    Input columns are matched to the model attributes by name. Columns the model
+    does not use are copied unchanged, and a missing attribute column is an error.
+    Empty cells and text in numeric columns match no selector, an unseen category
+    only matches the [a≠v] selectors of DNF models.
+
+Algorithm options:
+    --algorithm <name>          vertex-ac | edge-ac | exact | greedy-degree |
+                                greedy-clause | random-greedy [default: vertex-ac]
+    --time-limit <seconds>      time limit of every exact clique search, the best
+                                clique found so far is used when it is reached
+    --restarts <n>              random-greedy cliques built per search [default: 10]
+    --ants <n>                  ants per generation [default: 10]
+    --cycles <n>                generations per clique search [default: 10]
+    --alpha <f>                 pheromone exponent [default: 1]
+    --beta <f>                  heuristic exponent, 0 ignores the heuristic [default: 0]
+    --heuristic <name>          degree | clause, desirability of a candidate: its
+                                neighbors among the candidates or the values kept
+                                by the clique clause [default: degree]
+    --rho <0-1>                 pheromone persistence per generation [default: 0.99]
+    --tau-max <f>               upper pheromone bound [default: 6]
+    --tau-min <f>               lower pheromone bound [default: 0.01]
+    --pheromone-update <name>   cliques that deposit pheromone after a generation:
+                                iteration-best | global-best | elitist |
+                                rank-based | max-min [default: iteration-best]
+    --rank-ants <w>             rank-based: best so far plus the w - 1 best ants
+                                [default: 5]
+    --stagnation <n>            max-min: generations without improvement before
+                                the trails are reset [default: 5]
+    --no-improvement <n>        stop a clique search after n generations without
+                                a larger clique
+    --time-budget <seconds>     stop a clique search after this time
+    --ants to --time-budget only apply to vertex-ac and edge-ac, and a flag of an
+    algorithm other than the selected one is an error.";
+
+const DATASET_FLAGS: [&str; 4] = ["dataset", "positive-class", "class-column", "seed"];
+
+const TRAINING_FLAGS: [&str; 5] = [
+    "threads",
+    "prune",
+    "positive-tolerance",
+    "negative-tolerance",
+    "form",
+];
+
+const ALGORITHM_FLAGS: [&str; 16] = [
+    "algorithm",
+    "time-limit",
+    "restarts",
+    "ants",
+    "cycles",
+    "alpha",
+    "beta",
+    "heuristic",
+    "rho",
+    "tau-max",
+    "tau-min",
+    "pheromone-update",
+    "rank-ants",
+    "stagnation",
+    "no-improvement",
+    "time-budget",
+];
+
+const RUN_FLAGS: [&str; 5] = [
+    "learning-frac",
+    "split",
+    "testing-dataset",
+    "save-model",
+    "save-dnf-model",
+];
+
+const CROSS_VALIDATION_FLAGS: [&str; 1] = ["folds"];
+
+// every class is learned, so there is no positive class
+const MULTI_CLASS_FLAGS: [&str; 6] = [
+    "dataset",
+    "class-column",
+    "seed",
+    "strategy",
+    "learning-frac",
+    "split",
+];
+
+const PREDICT_FLAGS: [&str; 5] = [
+    "model",
+    "input",
+    "output",
+    "predicted-column",
+    "negative-label",
+];
 
 #[derive(Debug)]
 pub enum Config {
     Help(),
-    Run(String, String, String, usize, Box<Algorithm>),
+    Run(Box<RunConfig>),
+    CrossValidate(Box<CrossValidationConfig>),
+    MultiClass(Box<MultiClassConfig>),
+    Predict(PredictConfig),
+}
+
+#[derive(Debug)]
+pub struct RunConfig {
+    pub dataset: String,
+    pub class_column: String,
+    pub positive_class: String,
+    pub learning_frac: usize,
+    pub split: SplitMethod,
+    pub testing_dataset: Option<String>,
+    pub save_model: Option<String>,
+    pub save_dnf_model: Option<String>,
+    pub seed: u64,
+    pub training: TrainingOptions,
+    pub algorithm: Algorithm,
+}
+
+#[derive(Debug)]
+pub struct CrossValidationConfig {
+    pub dataset: String,
+    pub class_column: String,
+    pub positive_class: String,
+    pub folds: usize,
+    pub seed: u64,
+    pub training: TrainingOptions,
+    pub algorithm: Algorithm,
 }
 
 #[derive(Debug)]
+pub struct MultiClassConfig {
+    pub dataset: String,
+    pub class_column: String,
+    pub strategy: MultiClassStrategy,
+    pub learning_frac: usize,
+    pub split: SplitMethod,
+    pub seed: u64,
+    pub training: TrainingOptions,
+    pub algorithm: Algorithm,
+}
+
+#[derive(Debug)]
+pub struct PredictConfig {
+    pub model: String,
+    pub input: String,
+    // the predictions are written to stdout when there is no output file
+    pub output: Option<String>,
+    pub predicted_column: String,
+    pub negative_label: String,
+}
+
+#[derive(Clone, Debug)]
 pub enum Algorithm {
-    Ants(ACOAlgorithm, ACOParameters),
+    Ants(ACOAlgorithm, Box<ACOParameters>),
+    // branch and bound with an optional time limit for every clique search
+    Exact(Option<Duration>),
+    Greedy(GreedyStrategy),
 }
 
 impl Config {
     // Create new config
-    pub fn new(args: &[String]) -> Result<Config, &str> {
+    pub fn new(args: &[String]) -> Result<Config, String> {
         if args.len() < 2 {
-            return Err("Not enough arguments");
+            return Err("Not enough arguments".to_string());
         }
 
         match args[1].as_str() {
             "help" | "-h" | "--help" => Ok(Config::Help()),
             "run" => {
-                if args.len() < 7 {
-                    return Err("Not enough arguments");
-                }
-
-                let dataset = args[2][3..].to_string();
-                let class_column = args[3][10..].to_string();
-                let positive_class = args[4][8..].to_string();
-                let learning_frac = args[5][7..].parse::<usize>().unwrap();
-                let algorithm = args[6][5..].to_string();
-
-                match algorithm.as_str() {
-                    "vertex-ac" | "edge-ac" => {
-                        if args.len() < 13 {
-                            return Err("Not enough arguments");
-                        }
-
-                        let ants = args[7][5..].parse::<usize>().unwrap();
-                        let gen = args[8][4..].parse::<usize>().unwrap();
-                        let alpha = args[9][6..].parse::<f64>().unwrap();
-                        let rho = args[10][4..].parse::<f64>().unwrap();
-                        let tau_max = args[11][8..].parse::<f64>().unwrap();
-                        let tau_min = args[12][8..].parse::<f64>().unwrap();
-
-                        let algo = Algorithm::Ants(
-                            if algorithm.as_str() == "vertex-ac" {
-                                ACOAlgorithm::VertexAC
-                            } else {
-                                ACOAlgorithm::EdgeAC
-                            },
-                            ACOParameters::new(gen, ants, alpha, rho, tau_max, tau_min),
-                        );
-
-                        Ok(Config::Run(
-                            dataset,
-                            class_column,
-                            positive_class,
-                            learning_frac,
-                            Box::new(algo),
-                        ))
+                let flags = Flags::parse(
+                    &args[2..],
+                    &[
+                        &DATASET_FLAGS,
+                        &TRAINING_FLAGS,
+                        &ALGORITHM_FLAGS,
+                        &RUN_FLAGS,
+                    ],
+                )?;
+                Ok(Config::Run(Box::new(RunConfig::from_flags(&flags)?)))
+            }
+            "cross-validate" => {
+                let flags = Flags::parse(
+                    &args[2..],
+                    &[
+                        &DATASET_FLAGS,
+                        &TRAINING_FLAGS,
+                        &ALGORITHM_FLAGS,
+                        &CROSS_VALIDATION_FLAGS,
+                    ],
+                )?;
+                Ok(Config::CrossValidate(Box::new(
+                    CrossValidationConfig::from_flags(&flags)?,
+                )))
+            }
+            "multi-class" => {
+                let flags = Flags::parse(
+                    &args[2..],
+                    &[&MULTI_CLASS_FLAGS, &TRAINING_FLAGS, &ALGORITHM_FLAGS],
+                )?;
+                Ok(Config::MultiClass(Box::new(MultiClassConfig::from_flags(
+                    &flags,
+                )?)))
+            }
+            "predict" => {
+                let flags = Flags::parse(&args[2..], &[&PREDICT_FLAGS])?;
+                Ok(Config::Predict(PredictConfig {
+                    model: flags.required("model")?,
+                    input: flags.required("input")?,
+                    output: flags.optional("output")?,
+                    predicted_column: flags.get_or("predicted-column", "predicted".to_string())?,
+                    negative_label: flags.get_or("negative-label", "other".to_string())?,
+                }))
+            }
+            other => Err(format!("Invalid command '{}'", other)),
+        }
+    }
+}
+
+impl RunConfig {
+    fn from_flags(flags: &Flags) -> Result<RunConfig, String> {
+        let (learning_frac, split) = parse_split(flags)?;
+        let testing_dataset = flags.optional("testing-dataset")?;
+        if testing_dataset.is_some() {
+            for flag in ["learning-frac", "split"] {
+                if flags.contains(flag) {
+                    return Err(format!(
+                        "Flag --{} cannot be used together with --testing-dataset",
+                        flag
+                    ));
+                }
+            }
+        }
+        let seed = parse_seed(flags)?;
+        let training = parse_training_options(flags)?;
+        for (flag, form) in [
+            ("save-model", RuleForm::Cnf),
+            ("save-dnf-model", RuleForm::Dnf),
+        ] {
+            if flags.contains(flag) && !training.forms.contains(&form) {
+                return Err(format!(
+                    "Flag --{} needs a {} model, see --form",
+                    flag, form
+                ));
+            }
+        }
+
+        Ok(RunConfig {
+            dataset: flags.required("dataset")?,
+            class_column: flags.get_or("class-column", "class".to_string())?,
+            positive_class: flags.required("positive-class")?,
+            learning_frac,
+            split,
+            testing_dataset,
+            save_model: flags.optional("save-model")?,
+            save_dnf_model: flags.optional("save-dnf-model")?,
+            seed,
+            training,
+            algorithm: Algorithm::from_flags(flags, seed)?,
+        })
+    }
+}
+
+impl CrossValidationConfig {
+    fn from_flags(flags: &Flags) -> Result<CrossValidationConfig, String> {
+        let folds = flags.get_or("folds", 5)?;
+        if folds < 2 {
+            return Err(format!(
+                "Invalid value '{}' for --folds: expected at least 2 folds",
+                folds
+            ));
+        }
+        let seed = parse_seed(flags)?;
+
+        Ok(CrossValidationConfig {
+            dataset: flags.required("dataset")?,
+            class_column: flags.get_or("class-column", "class".to_string())?,
+            positive_class: flags.required("positive-class")?,
+            folds,
+            seed,
+            training: parse_training_options(flags)?,
+            algorithm: Algorithm::from_flags(flags, seed)?,
+        })
+    }
+}
+
+impl MultiClassConfig {
+    fn from_flags(flags: &Flags) -> Result<MultiClassConfig, String> {
+        let strategy = match flags
+            .get_or("strategy", "one-vs-rest".to_string())?
+            .as_str()
+        {
+            "one-vs-rest" => MultiClassStrategy::OneVsRest,
+            "one-vs-one" => MultiClassStrategy::OneVsOne,
+            other => {
+                return Err(format!(
+                    "Invalid value '{}' for --strategy: expected one-vs-rest or one-vs-one",
+                    other
+                ))
+            }
+        };
+        let (learning_frac, split) = parse_split(flags)?;
+        let seed = parse_seed(flags)?;
+
+        Ok(MultiClassConfig {
+            dataset: flags.required("dataset")?,
+            class_column: flags.get_or("class-column", "class".to_string())?,
+            strategy,
+            learning_frac,
+            split,
+            seed,
+            training: parse_training_options(flags)?,
+            algorithm: Algorithm::from_flags(flags, seed)?,
+        })
+    }
+}
+
+fn parse_split(flags: &Flags) -> Result<(usize, SplitMethod), String> {
+    let learning_frac = flags.get_or("learning-frac", 80)?;
+    if !(1..=100).contains(&learning_frac) {
+        return Err(format!(
+            "Invalid value '{}' for --learning-frac: expected a percentage between 1 and 100",
+            learning_frac
+        ));
+    }
+
+    let split = match flags.get_or("split", "random".to_string())?.as_str() {
+        "random" => SplitMethod::Random,
+        "stratified" => SplitMethod::Stratified,
+        other => {
+            return Err(format!(
+                "Invalid value '{}' for --split: expected random or stratified",
+                other
+            ))
+        }
+    };
+
+    Ok((learning_frac, split))
+}
+
+// the seed is resolved once so the value actually used can be reported
+fn parse_seed(flags: &Flags) -> Result<u64, String> {
+    match flags.get_or("seed", "1000".to_string())?.as_str() {
+        "entropy" => Ok(rand::random()),
+        seed => parse_value("seed", seed),
+    }
+}
+
+fn parse_training_options(flags: &Flags) -> Result<TrainingOptions, String> {
+    let threads = flags.get_or(
+        "threads",
+        thread::available_parallelism().map_or(1, |n| n.get()),
+    )?;
+    if threads == 0 {
+        return Err("Invalid value '0' for --threads: expected at least one thread".to_string());
+    }
+
+    let mut tolerance = NoiseTolerance::default();
+    for (flag, fraction) in [
+        ("positive-tolerance", &mut tolerance.positives),
+        ("negative-tolerance", &mut tolerance.negatives),
+    ] {
+        *fraction = flags.get_or(flag, 0.0)?;
+        if !(0.0..=1.0).contains(fraction) {
+            return Err(format!(
+                "Invalid value '{}' for --{}: expected a fraction in [0, 1]",
+                fraction, flag
+            ));
+        }
+    }
+
+    let forms: &'static [RuleForm] = match flags.get_or("form", "cnf".to_string())?.as_str() {
+        "cnf" => &[RuleForm::Cnf],
+        "dnf" => &[RuleForm::Dnf],
+        "both" => &[RuleForm::Cnf, RuleForm::Dnf],
+        other => {
+            return Err(format!(
+                "Invalid value '{}' for --form: expected cnf, dnf or both",
+                other
+            ))
+        }
+    };
+
+    Ok(TrainingOptions {
+        threads,
+        prune: flags.get_or("prune", false)?,
+        tolerance,
+        forms,
+    })
+}
+
+impl Algorithm {
+    // name and value of every training parameter, as given on the command line
+    pub fn parameters(&self) -> Vec<(String, String)> {
+        match self {
+            Algorithm::Ants(aco_algorithm, p) => {
+                let name = match aco_algorithm {
+                    ACOAlgorithm::VertexAC => "vertex-ac",
+                    ACOAlgorithm::EdgeAC => "edge-ac",
+                };
+                let heuristic = match p.heuristic {
+                    ACOHeuristic::Degree => "degree",
+                    ACOHeuristic::ClauseValues => "clause",
+                };
+                let mut parameters = vec![
+                    ("algorithm".to_string(), name.to_string()),
+                    ("ants".to_string(), p.ants.to_string()),
+                    ("cycles".to_string(), p.cycles.to_string()),
+                    ("alpha".to_string(), p.alpha.to_string()),
+                    ("beta".to_string(), p.beta.to_string()),
+                    ("heuristic".to_string(), heuristic.to_string()),
+                    ("rho".to_string(), p.rho.to_string()),
+                    ("tau-max".to_string(), p.tau_max.to_string()),
+                    ("tau-min".to_string(), p.tau_min.to_string()),
+                ];
+                match p.pheromone_update {
+                    PheromoneUpdate::IterationBest => parameters
+                        .push(("pheromone-update".to_string(), "iteration-best".to_string())),
+                    PheromoneUpdate::GlobalBest => {
+                        parameters.push(("pheromone-update".to_string(), "global-best".to_string()))
+                    }
+                    PheromoneUpdate::Elitist => {
+                        parameters.push(("pheromone-update".to_string(), "elitist".to_string()))
+                    }
+                    PheromoneUpdate::RankBased(w) => {
+                        parameters.push(("pheromone-update".to_string(), "rank-based".to_string()));
+                        parameters.push(("rank-ants".to_string(), w.to_string()));
+                    }
+                    PheromoneUpdate::MaxMin(n) => {
+                        parameters.push(("pheromone-update".to_string(), "max-min".to_string()));
+                        parameters.push(("stagnation".to_string(), n.to_string()));
+                    }
+                }
+                if let Some(n) = p.max_cycles_without_improvement {
+                    parameters.push(("no-improvement".to_string(), n.to_string()));
+                }
+                if let Some(time_budget) = p.time_budget {
+                    parameters.push((
+                        "time-budget".to_string(),
+                        time_budget.as_secs_f64().to_string(),
+                    ));
+                }
+                parameters
+            }
+            Algorithm::Exact(time_limit) => {
+                let mut parameters = vec![("algorithm".to_string(), "exact".to_string())];
+                if let Some(time_limit) = time_limit {
+                    parameters.push((
+                        "time-limit".to_string(),
+                        time_limit.as_secs_f64().to_string(),
+                    ));
+                }
+                parameters
+            }
+            Algorithm::Greedy(strategy) => match strategy {
+                GreedyStrategy::Degree => {
+                    vec![("algorithm".to_string(), "greedy-degree".to_string())]
+                }
+                GreedyStrategy::ClauseLoss => {
+                    vec![("algorithm".to_string(), "greedy-clause".to_string())]
+                }
+                GreedyStrategy::Random(restarts) => vec![
+                    ("algorithm".to_string(), "random-greedy".to_string()),
+                    ("restarts".to_string(), restarts.to_string()),
+                ],
+            },
+        }
+    }
+
+    fn from_flags(flags: &Flags, seed: u64) -> Result<Algorithm, String> {
+        let algorithm = flags.get_or("algorithm", "vertex-ac".to_string())?;
+        let parsed = match algorithm.as_str() {
+            "vertex-ac" => Algorithm::ants_from_flags(flags, ACOAlgorithm::VertexAC, seed),
+            "edge-ac" => Algorithm::ants_from_flags(flags, ACOAlgorithm::EdgeAC, seed),
+            "exact" => {
+                let time_limit: Option<f64> = flags.optional("time-limit")?;
+                match time_limit {
+                    Some(seconds) if !(seconds >= 0.0 && seconds.is_finite()) => Err(format!(
+                        "Invalid value '{}' for --time-limit: expected a number of seconds",
+                        seconds
+                    )),
+                    _ => Ok(Algorithm::Exact(time_limit.map(Duration::from_secs_f64))),
+                }
+            }
+            "greedy-degree" => Ok(Algorithm::Greedy(GreedyStrategy::Degree)),
+            "greedy-clause" => Ok(Algorithm::Greedy(GreedyStrategy::ClauseLoss)),
+            "random-greedy" => {
+                let restarts = flags.get_or("restarts", 10)?;
+                if restarts == 0 {
+                    return Err(
+                        "Invalid value '0' for --restarts: expected at least one restart"
+                            .to_string(),
+                    );
+                }
+                Ok(Algorithm::Greedy(GreedyStrategy::Random(restarts)))
+            }
+            _ => Err(format!(
+                "Invalid value '{}' for --algorithm: expected vertex-ac, edge-ac, exact, \
+                 greedy-degree, greedy-clause or random-greedy",
+                algorithm
+            )),
+        }?;
+
+        // flags of the other algorithms would be silently ignored
+        for flag in &ALGORITHM_FLAGS[1..] {
+            let applies = match *flag {
+                "time-limit" => algorithm == "exact",
+                "restarts" => algorithm == "random-greedy",
+                _ => matches!(parsed, Algorithm::Ants(_, _)),
+            };
+            if flags.contains(flag) && !applies {
+                return Err(format!(
+                    "Flag --{} does not apply to --algorithm {}",
+                    flag, algorithm
+                ));
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    fn ants_from_flags(
+        flags: &Flags,
+        aco_algorithm: ACOAlgorithm,
+        seed: u64,
+    ) -> Result<Algorithm, String> {
+        let ants: usize = flags.get_or("ants", 10)?;
+        let cycles: usize = flags.get_or("cycles", 10)?;
+        let alpha: f64 = flags.get_or("alpha", 1.0)?;
+        let beta: f64 = flags.get_or("beta", 0.0)?;
+        let heuristic = match flags.get_or("heuristic", "degree".to_string())?.as_str() {
+            "degree" => ACOHeuristic::Degree,
+            "clause" => ACOHeuristic::ClauseValues,
+            other => {
+                return Err(format!(
+                    "Invalid value '{}' for --heuristic: expected degree or clause",
+                    other
+                ))
+            }
+        };
+        let rho: f64 = flags.get_or("rho", 0.99)?;
+        let tau_max: f64 = flags.get_or("tau-max", 6.0)?;
+        let tau_min: f64 = flags.get_or("tau-min", 0.01)?;
+
+        if ants == 0 {
+            return Err("Invalid value '0' for --ants: expected at least one ant".to_string());
+        }
+        if cycles == 0 {
+            return Err("Invalid value '0' for --cycles: expected at least one cycle".to_string());
+        }
+        if !(rho > 0.0 && rho <= 1.0) {
+            return Err(format!(
+                "Invalid value '{}' for --rho: expected a value in (0, 1]",
+                rho
+            ));
+        }
+        if !(tau_min > 0.0 && tau_min <= tau_max) {
+            return Err(format!(
+                "Invalid values for --tau-min ({}) and --tau-max ({}): expected 0 < tau-min <= tau-max",
+                tau_min, tau_max
+            ));
+        }
+
+        let pheromone_update = match flags
+            .get_or("pheromone-update", "iteration-best".to_string())?
+            .as_str()
+        {
+            "iteration-best" => PheromoneUpdate::IterationBest,
+            "global-best" => PheromoneUpdate::GlobalBest,
+            "elitist" => PheromoneUpdate::Elitist,
+            "rank-based" => {
+                let w: usize = flags.get_or("rank-ants", 5)?;
+                if w == 0 {
+                    return Err(
+                        "Invalid value '0' for --rank-ants: expected at least one ant".to_string(),
+                    );
+                }
+                PheromoneUpdate::RankBased(w)
+            }
+            "max-min" => {
+                let n: usize = flags.get_or("stagnation", 5)?;
+                if n == 0 {
+                    return Err(
+                        "Invalid value '0' for --stagnation: expected at least one cycle"
+                            .to_string(),
+                    );
+                }
+                PheromoneUpdate::MaxMin(n)
+            }
+            other => {
+                return Err(format!(
+                    "Invalid value '{}' for --pheromone-update: expected iteration-best, \
+                         global-best, elitist, rank-based or max-min",
+                    other
+                ))
+            }
+        };
+
+        let max_cycles_without_improvement: Option<usize> = flags.optional("no-improvement")?;
+        if max_cycles_without_improvement == Some(0) {
+            return Err(
+                "Invalid value '0' for --no-improvement: expected at least one cycle".to_string(),
+            );
+        }
+        let time_budget: Option<f64> = flags.optional("time-budget")?;
+        if let Some(seconds) = time_budget {
+            if !(seconds >= 0.0 && seconds.is_finite()) {
+                return Err(format!(
+                    "Invalid value '{}' for --time-budget: expected a number of seconds",
+                    seconds
+                ));
+            }
+        }
+
+        if !(alpha >= 0.0 && alpha.is_finite()) {
+            return Err(format!(
+                "Invalid value '{}' for --alpha: expected a non-negative number",
+                alpha
+            ));
+        }
+        if !(beta >= 0.0 && beta.is_finite()) {
+            return Err(format!(
+                "Invalid value '{}' for --beta: expected a non-negative number",
+                beta
+            ));
+        }
+
+        let mut parameters = ACOParameters::new(cycles, ants, alpha, rho, tau_max, tau_min, seed);
+        parameters.heuristic = heuristic;
+        parameters.beta = beta;
+        parameters.pheromone_update = pheromone_update;
+        parameters.max_cycles_without_improvement = max_cycles_without_improvement;
+        parameters.time_budget = time_budget.map(Duration::from_secs_f64);
+        Ok(Algorithm::Ants(aco_algorithm, Box::new(parameters)))
+    }
+}
+
+// named flags of a command, given as `--name value` or `--name=value`
+#[derive(Debug)]
+struct Flags {
+    values: HashMap<String, String>,
+}
+
+impl Flags {
+    fn parse(args: &[String], known: &[&[&str]]) -> Result<Flags, String> {
+        let mut values = HashMap::new();
+        let mut idx = 0;
+
+        while idx < args.len() {
+            let arg = &args[idx];
+            let name_value = match arg.strip_prefix("--") {
+                Some(name_value) => name_value,
+                None => return Err(format!("Unexpected argument '{}'", arg)),
+            };
+
+            let (name, value) = match name_value.split_once('=') {
+                Some((name, value)) => (name.to_string(), value.to_string()),
+                None => {
+                    idx += 1;
+                    match args.get(idx) {
+                        Some(value) => (name_value.to_string(), value.clone()),
+                        None => return Err(format!("Missing value for --{}", name_value)),
+                    }
+                }
+            };
+
+            if !known.iter().any(|flags| flags.contains(&name.as_str())) {
+                return Err(format!("Unknown flag --{}", name));
+            }
+            if values.insert(name.clone(), value).is_some() {
+                return Err(format!("Flag --{} given more than once", name));
+            }
+            idx += 1;
+        }
+
+        Ok(Flags { values })
+    }
+
+    fn required<T: FromStr>(&self, name: &str) -> Result<T, String> {
+        match self.values.get(name) {
+            Some(value) => parse_value(name, value),
+            None => Err(format!("Missing required flag --{}", name)),
+        }
+    }
+
+    fn optional<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.values
+            .get(name)
+            .map(|value| parse_value(name, value))
+            .transpose()
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+    }
+
+    fn get_or<T: FromStr>(&self, name: &str, default: T) -> Result<T, String> {
+        match self.values.get(name) {
+            Some(value) => parse_value(name, value),
+            None => Ok(default),
+        }
+    }
+}
+
+fn parse_value<T: FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse::<T>()
+        .map_err(|_| format!("Invalid value '{}' for --{}", value, name))
+}
+
+// tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_run_flags_in_any_order() {
+        let config = Config::new(&args(
+            "oqat run --algorithm=edge-ac --positive-class yes --ants 3 --dataset datasets/test1.csv",
+        ))
+        .unwrap();
+
+        match config {
+            Config::Run(run) => {
+                assert_eq!(run.dataset, "datasets/test1.csv");
+                assert_eq!(run.class_column, "class");
+                assert_eq!(run.positive_class, "yes");
+                assert_eq!(run.learning_frac, 80);
+                assert_eq!(run.split, SplitMethod::Random);
+                assert_eq!(run.seed, 1000);
+                match run.algorithm {
+                    Algorithm::Ants(ACOAlgorithm::EdgeAC, p) => {
+                        assert_eq!(p.ants, 3);
+                        assert_eq!(p.cycles, 10);
+                        assert_eq!(p.rho, 0.99);
                     }
-                    _ => Err("Algorithm not found"),
+                    _ => panic!("expected edge-ac"),
+                }
+            }
+            _ => panic!("expected run config"),
+        }
+    }
+
+    #[test]
+    fn test_seed() {
+        let config = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --seed 42",
+        ))
+        .unwrap();
+        assert!(matches!(config, Config::Run(run) if run.seed == 42));
+
+        let config = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --seed entropy",
+        ));
+        assert!(config.is_ok());
+
+        let err = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --seed -1",
+        ))
+        .unwrap_err();
+        assert_eq!(err, "Invalid value '-1' for --seed");
+    }
+
+    #[test]
+    fn test_testing_dataset() {
+        let config = Config::new(&args(
+            "oqat run --dataset a.csv --testing-dataset b.csv --positive-class yes",
+        ))
+        .unwrap();
+        assert!(
+            matches!(config, Config::Run(run) if run.testing_dataset == Some("b.csv".to_string()))
+        );
+
+        let err = Config::new(&args(
+            "oqat run --dataset a.csv --testing-dataset b.csv --positive-class yes --split stratified",
+        ))
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "Flag --split cannot be used together with --testing-dataset"
+        );
+    }
+
+    #[test]
+    fn test_cross_validate() {
+        let config = Config::new(&args(
+            "oqat cross-validate --dataset a.csv --positive-class yes --folds 3 --cycles 2",
+        ))
+        .unwrap();
+        match config {
+            Config::CrossValidate(cv) => {
+                assert_eq!(cv.folds, 3);
+                assert!(matches!(cv.algorithm, Algorithm::Ants(_, p) if p.cycles == 2));
+            }
+            _ => panic!("expected cross-validate config"),
+        }
+
+        let err = Config::new(&args(
+            "oqat cross-validate --dataset a.csv --positive-class yes --split random",
+        ))
+        .unwrap_err();
+        assert_eq!(err, "Unknown flag --split");
+
+        let err = Config::new(&args(
+            "oqat cross-validate --dataset a.csv --positive-class yes --folds 1",
+        ))
+        .unwrap_err();
+        assert!(err.starts_with("Invalid value '1' for --folds"));
+    }
+
+    #[test]
+    fn test_predict() {
+        let config = Config::new(&args(
+            "oqat predict --input new.csv --model m.txt --negative-label no",
+        ))
+        .unwrap();
+        match config {
+            Config::Predict(predict) => {
+                assert_eq!(predict.model, "m.txt");
+                assert_eq!(predict.input, "new.csv");
+                assert_eq!(predict.output, None);
+                assert_eq!(predict.predicted_column, "predicted");
+                assert_eq!(predict.negative_label, "no");
+            }
+            _ => panic!("expected predict config"),
+        }
+
+        let err = Config::new(&args("oqat predict --input new.csv")).unwrap_err();
+        assert_eq!(err, "Missing required flag --model");
+    }
+
+    #[test]
+    fn test_exact_algorithm() {
+        let config = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --algorithm exact --time-limit 1.5",
+        ))
+        .unwrap();
+        match config {
+            Config::Run(run) => match run.algorithm {
+                Algorithm::Exact(time_limit) => {
+                    assert_eq!(time_limit, Some(Duration::from_millis(1500)))
+                }
+                _ => panic!("expected exact algorithm"),
+            },
+            _ => panic!("expected run config"),
+        }
+
+        let err = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --algorithm exact --time-limit -1",
+        ))
+        .unwrap_err();
+        assert!(err.starts_with("Invalid value '-1' for --time-limit"));
+    }
+
+    #[test]
+    fn test_flags_of_other_algorithms() {
+        for (line, error) in [
+            (
+                "--ants 50 --algorithm exact",
+                "Flag --ants does not apply to --algorithm exact",
+            ),
+            (
+                "--time-limit 2",
+                "Flag --time-limit does not apply to --algorithm vertex-ac",
+            ),
+            (
+                "--algorithm greedy-degree --restarts 3",
+                "Flag --restarts does not apply to --algorithm greedy-degree",
+            ),
+            (
+                "--algorithm random-greedy --time-budget 1",
+                "Flag --time-budget does not apply to --algorithm random-greedy",
+            ),
+        ] {
+            let err = Config::new(&args(&format!(
+                "oqat run --dataset a.csv --positive-class yes {}",
+                line
+            )))
+            .unwrap_err();
+            assert_eq!(err, error);
+        }
+    }
+
+    #[test]
+    fn test_greedy_algorithms() {
+        let algorithm = |line: &str| match Config::new(&args(line)).unwrap() {
+            Config::Run(run) => run.algorithm,
+            _ => panic!("expected run config"),
+        };
+
+        assert!(matches!(
+            algorithm("oqat run --dataset a.csv --positive-class yes --algorithm greedy-degree"),
+            Algorithm::Greedy(GreedyStrategy::Degree)
+        ));
+        assert!(matches!(
+            algorithm("oqat run --dataset a.csv --positive-class yes --algorithm greedy-clause"),
+            Algorithm::Greedy(GreedyStrategy::ClauseLoss)
+        ));
+        assert!(matches!(
+            algorithm(
+                "oqat run --dataset a.csv --positive-class yes --algorithm random-greedy --restarts 4"
+            ),
+            Algorithm::Greedy(GreedyStrategy::Random(4))
+        ));
+    }
+
+    #[test]
+    fn test_heuristic() {
+        let config = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --beta 2 --heuristic clause",
+        ))
+        .unwrap();
+        match config {
+            Config::Run(run) => match run.algorithm {
+                Algorithm::Ants(_, p) => {
+                    assert_eq!(p.beta, 2.0);
+                    assert_eq!(p.heuristic, ACOHeuristic::ClauseValues);
+                }
+                _ => panic!("expected vertex-ac"),
+            },
+            _ => panic!("expected run config"),
+        }
+
+        let err = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --heuristic size",
+        ))
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "Invalid value 'size' for --heuristic: expected degree or clause"
+        );
+
+        for alpha in ["-1", "inf", "NaN"] {
+            let err = Config::new(&args(&format!(
+                "oqat run --dataset a.csv --positive-class yes --alpha {}",
+                alpha
+            )))
+            .unwrap_err();
+            assert!(err.starts_with("Invalid value"), "{}", err);
+            assert!(err.ends_with("for --alpha: expected a non-negative number"));
+        }
+    }
+
+    #[test]
+    fn test_pheromone_update() {
+        let pheromone_update = |line: &str| match Config::new(&args(line)).unwrap() {
+            Config::Run(run) => match run.algorithm {
+                Algorithm::Ants(_, p) => p.pheromone_update,
+                _ => panic!("expected vertex-ac"),
+            },
+            _ => panic!("expected run config"),
+        };
+
+        assert_eq!(
+            pheromone_update("oqat run --dataset a.csv --positive-class yes"),
+            PheromoneUpdate::IterationBest
+        );
+        assert_eq!(
+            pheromone_update(
+                "oqat run --dataset a.csv --positive-class yes --pheromone-update elitist"
+            ),
+            PheromoneUpdate::Elitist
+        );
+        assert_eq!(
+            pheromone_update(
+                "oqat run --dataset a.csv --positive-class yes --pheromone-update rank-based --rank-ants 3"
+            ),
+            PheromoneUpdate::RankBased(3)
+        );
+        assert_eq!(
+            pheromone_update(
+                "oqat run --dataset a.csv --positive-class yes --pheromone-update max-min"
+            ),
+            PheromoneUpdate::MaxMin(5)
+        );
+    }
+
+    #[test]
+    fn test_stop_criteria() {
+        let config = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --no-improvement 20 --time-budget 1.5",
+        ))
+        .unwrap();
+        match config {
+            Config::Run(run) => match run.algorithm {
+                Algorithm::Ants(_, p) => {
+                    assert_eq!(p.max_cycles_without_improvement, Some(20));
+                    assert_eq!(p.time_budget, Some(Duration::from_millis(1500)));
+                }
+                _ => panic!("expected vertex-ac"),
+            },
+            _ => panic!("expected run config"),
+        }
+
+        let err = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --time-budget -1",
+        ))
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "Invalid value '-1' for --time-budget: expected a number of seconds"
+        );
+    }
+
+    #[test]
+    fn test_threads() {
+        let config = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --threads 3",
+        ))
+        .unwrap();
+        assert!(matches!(config, Config::Run(run) if run.training.threads == 3));
+
+        let config = Config::new(&args(
+            "oqat cross-validate --dataset a.csv --positive-class yes --threads 3",
+        ))
+        .unwrap();
+        assert!(matches!(config, Config::CrossValidate(cv) if cv.training.threads == 3));
+
+        let err = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --threads 0",
+        ))
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "Invalid value '0' for --threads: expected at least one thread"
+        );
+    }
+
+    #[test]
+    fn test_prune() {
+        let prune = |line: &str| match Config::new(&args(line)).unwrap() {
+            Config::Run(run) => run.training.prune,
+            _ => panic!("expected run config"),
+        };
+
+        assert!(!prune("oqat run --dataset a.csv --positive-class yes"));
+        assert!(prune(
+            "oqat run --dataset a.csv --positive-class yes --prune true"
+        ));
+
+        let err = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --prune maybe",
+        ))
+        .unwrap_err();
+        assert_eq!(err, "Invalid value 'maybe' for --prune");
+    }
+
+    #[test]
+    fn test_noise_tolerance() {
+        let config = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --positive-tolerance 0.1 --negative-tolerance 0.05",
+        ))
+        .unwrap();
+        match config {
+            Config::Run(run) => assert_eq!(
+                run.training.tolerance,
+                NoiseTolerance {
+                    positives: 0.1,
+                    negatives: 0.05
                 }
+            ),
+            _ => panic!("expected run config"),
+        }
+
+        let err = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --negative-tolerance 1.5",
+        ))
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "Invalid value '1.5' for --negative-tolerance: expected a fraction in [0, 1]"
+        );
+    }
+
+    #[test]
+    fn test_form() {
+        let forms = |line: &str| match Config::new(&args(line)).unwrap() {
+            Config::Run(run) => run.training.forms,
+            Config::CrossValidate(cv) => cv.training.forms,
+            _ => panic!("expected run or cross-validate config"),
+        };
+        assert_eq!(
+            forms("oqat run --dataset a.csv --positive-class yes"),
+            [RuleForm::Cnf]
+        );
+        assert_eq!(
+            forms("oqat run --dataset a.csv --positive-class yes --form dnf"),
+            [RuleForm::Dnf]
+        );
+        assert_eq!(
+            forms("oqat cross-validate --dataset a.csv --positive-class yes --form both"),
+            [RuleForm::Cnf, RuleForm::Dnf]
+        );
+
+        let config = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --form both --save-model c.txt --save-dnf-model d.txt",
+        ))
+        .unwrap();
+        match config {
+            Config::Run(run) => {
+                assert_eq!(run.save_model, Some("c.txt".to_string()));
+                assert_eq!(run.save_dnf_model, Some("d.txt".to_string()));
             }
-            _ => Err("Invalid argument"),
+            _ => panic!("expected run config"),
         }
+
+        let err = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --save-dnf-model d.txt",
+        ))
+        .unwrap_err();
+        assert_eq!(err, "Flag --save-dnf-model needs a DNF model, see --form");
+        let err = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --form dnf --save-model c.txt",
+        ))
+        .unwrap_err();
+        assert_eq!(err, "Flag --save-model needs a CNF model, see --form");
+        let err = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --form cnf,dnf",
+        ))
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "Invalid value 'cnf,dnf' for --form: expected cnf, dnf or both"
+        );
+    }
+
+    #[test]
+    fn test_multi_class() {
+        let config = Config::new(&args(
+            "oqat multi-class --dataset datasets/hayes_roth.csv --strategy one-vs-one --split stratified --form dnf",
+        ))
+        .unwrap();
+        match config {
+            Config::MultiClass(multi_class) => {
+                assert_eq!(multi_class.dataset, "datasets/hayes_roth.csv");
+                assert_eq!(multi_class.class_column, "class");
+                assert_eq!(multi_class.strategy, MultiClassStrategy::OneVsOne);
+                assert_eq!(multi_class.learning_frac, 80);
+                assert_eq!(multi_class.split, SplitMethod::Stratified);
+                assert_eq!(multi_class.training.forms, [RuleForm::Dnf]);
+            }
+            _ => panic!("expected multi-class config"),
+        }
+
+        match Config::new(&args("oqat multi-class --dataset a.csv")).unwrap() {
+            Config::MultiClass(multi_class) => {
+                assert_eq!(multi_class.strategy, MultiClassStrategy::OneVsRest)
+            }
+            _ => panic!("expected multi-class config"),
+        }
+
+        let err =
+            Config::new(&args("oqat multi-class --dataset a.csv --positive-class 1")).unwrap_err();
+        assert_eq!(err, "Unknown flag --positive-class");
+        let err =
+            Config::new(&args("oqat multi-class --dataset a.csv --strategy pairs")).unwrap_err();
+        assert_eq!(
+            err,
+            "Invalid value 'pairs' for --strategy: expected one-vs-rest or one-vs-one"
+        );
+    }
+
+    #[test]
+    fn test_help() {
+        assert!(matches!(
+            Config::new(&args("oqat --help")),
+            Ok(Config::Help())
+        ));
+    }
+
+    #[test]
+    fn test_invalid_flags_are_reported() {
+        let err = Config::new(&args("oqat run --dataset a.csv")).unwrap_err();
+        assert_eq!(err, "Missing required flag --positive-class");
+
+        let err = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --ant 3",
+        ))
+        .unwrap_err();
+        assert_eq!(err, "Unknown flag --ant");
+
+        let err = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --ants x",
+        ))
+        .unwrap_err();
+        assert_eq!(err, "Invalid value 'x' for --ants");
+
+        let err =
+            Config::new(&args("oqat run --dataset a.csv --positive-class yes --rho")).unwrap_err();
+        assert_eq!(err, "Missing value for --rho");
+
+        let err = Config::new(&args(
+            "oqat run --dataset a.csv --positive-class yes --learning-frac 120",
+        ))
+        .unwrap_err();
+        assert!(err.starts_with("Invalid value '120' for --learning-frac"));
     }
 }
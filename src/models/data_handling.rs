@@ -1,5 +1,10 @@
+pub mod attribute_kind;
 pub mod attribute_value;
 pub mod attribute_values_set;
 pub mod dataset;
+pub mod dataset_error;
+pub mod encoded_values_set;
+pub mod multi_class_dataset;
 pub mod row;
 pub mod unit_tests;
+pub mod value_dictionary;
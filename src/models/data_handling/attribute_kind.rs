@@ -0,0 +1,28 @@
+use std::{fmt, str::FromStr};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttributeKind {
+    Numeric,
+    Categorical,
+}
+
+impl fmt::Display for AttributeKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AttributeKind::Numeric => write!(f, "numeric"),
+            AttributeKind::Categorical => write!(f, "categorical"),
+        }
+    }
+}
+
+impl FromStr for AttributeKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "numeric" => Ok(AttributeKind::Numeric),
+            "categorical" => Ok(AttributeKind::Categorical),
+            _ => Err(format!("Unknown attribute kind '{}'", s)),
+        }
+    }
+}
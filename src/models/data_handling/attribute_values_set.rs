@@ -32,11 +32,37 @@ impl fmt::Display for AttributeValuesSet {
     }
 }
 
+impl Default for AttributeValuesSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AttributeValuesSet {
     pub fn new() -> AttributeValuesSet {
         AttributeValuesSet::Empty
     }
 
+    // a csv field is numeric when it parses as a number, categorical otherwise
+    pub fn from_field(attr_name: &str, field: &str) -> AttributeValuesSet {
+        match field.parse::<f64>() {
+            Ok(num) => {
+                AttributeValuesSet::Num(attr_name.to_string(), HashSet::from([OrderedFloat(num)]))
+            }
+            Err(_) => {
+                AttributeValuesSet::Cat(attr_name.to_string(), HashSet::from([field.to_string()]))
+            }
+        }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            AttributeValuesSet::Num(name, _) => Some(name),
+            AttributeValuesSet::Cat(name, _) => Some(name),
+            AttributeValuesSet::Empty => None,
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         match self {
             AttributeValuesSet::Num(_, set) => set.is_empty(),
@@ -46,7 +72,7 @@ impl AttributeValuesSet {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct AttributeValuesSetList {
     pub list: Vec<AttributeValuesSet>,
 }
@@ -74,6 +100,18 @@ impl AttributeValuesSetList {
         self.list.is_empty()
     }
 
+    // number of values in all the sets of the list
+    pub fn n_values(&self) -> usize {
+        self.list
+            .iter()
+            .map(|set| match set {
+                AttributeValuesSet::Num(_, values) => values.len(),
+                AttributeValuesSet::Cat(_, values) => values.len(),
+                AttributeValuesSet::Empty => 0,
+            })
+            .sum()
+    }
+
     pub fn union(&self, other: &AttributeValuesSetList) -> AttributeValuesSetList {
         let mut result = self.clone();
         for attr_idx in 0..result.list.len() {
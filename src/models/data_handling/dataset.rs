@@ -1,10 +1,11 @@
-use std::{collections::HashSet, fmt};
+use std::fmt;
 
-use ordered_float::OrderedFloat;
 use rand::{rngs::StdRng, Rng};
 
 use super::{
+    attribute_kind::AttributeKind,
     attribute_values_set::{AttributeValuesSet, AttributeValuesSetList},
+    dataset_error::DatasetError,
     row::Row,
 };
 
@@ -36,24 +37,262 @@ impl fmt::Display for Dataset {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitMethod {
+    // rows are drawn uniformly at random from the whole file
+    Random,
+    // rows are drawn separately from each class so both keep their ratio
+    Stratified,
+}
+
 impl Dataset {
     pub fn new(
+        rng: StdRng,
+        path: &str,
+        class_column: &str,
+        positive_class: &str,
+        learning_frac: usize,
+    ) -> Result<Dataset, DatasetError> {
+        Dataset::with_split(
+            rng,
+            path,
+            class_column,
+            positive_class,
+            learning_frac,
+            SplitMethod::Random,
+        )
+    }
+
+    pub fn with_split(
         mut rng: StdRng,
         path: &str,
         class_column: &str,
         positive_class: &str,
         learning_frac: usize,
-    ) -> Dataset {
-        let mut reader = csv::Reader::from_path(path).unwrap();
-        let headers = reader.headers().unwrap().clone();
+        split: SplitMethod,
+    ) -> Result<Dataset, DatasetError> {
+        let all_records = Dataset::read_rows(path, class_column)?;
+        Dataset::check_classes(&all_records, positive_class)?;
+
+        let (learning, testing) = match split {
+            SplitMethod::Random => Dataset::random_split(&mut rng, all_records, learning_frac),
+            SplitMethod::Stratified => {
+                let (positives, negatives): (Vec<Row>, Vec<Row>) = all_records
+                    .into_iter()
+                    .partition(|row| row.class == positive_class);
+                let (mut learning, mut testing) =
+                    Dataset::stratum_split(&mut rng, positives, learning_frac);
+                let (learning_neg, testing_neg) =
+                    Dataset::stratum_split(&mut rng, negatives, learning_frac);
+                learning.extend(learning_neg);
+                testing.extend(testing_neg);
+                (learning, testing)
+            }
+        };
+
+        let (learning_pos, learning_neg): (Vec<Row>, Vec<Row>) = learning
+            .into_iter()
+            .partition(|row| row.class == positive_class);
+        let (testing_pos, testing_neg): (Vec<Row>, Vec<Row>) = testing
+            .into_iter()
+            .partition(|row| row.class == positive_class);
+
+        // the rejectability graph needs at least one example of each class to learn from
+        if learning_pos.is_empty() {
+            return Err(DatasetError::NoLearningPositives(learning_frac));
+        }
+        if learning_neg.is_empty() {
+            return Err(DatasetError::NoLearningNegatives(learning_frac));
+        }
+
+        Ok(Dataset {
+            learning_pos,
+            learning_neg,
+            testing_pos,
+            testing_neg,
+        })
+    }
+
+    // split the file into k stratified folds, the i-th dataset tests on the
+    // i-th fold and learns from the other k - 1
+    pub fn k_folds(
+        mut rng: StdRng,
+        path: &str,
+        class_column: &str,
+        positive_class: &str,
+        k: usize,
+    ) -> Result<Vec<Dataset>, DatasetError> {
+        let all_records = Dataset::read_rows(path, class_column)?;
+        Dataset::check_classes(&all_records, positive_class)?;
+
+        let (positives, negatives): (Vec<Row>, Vec<Row>) = all_records
+            .into_iter()
+            .partition(|row| row.class == positive_class);
+        if positives.len() < k || negatives.len() < k {
+            return Err(DatasetError::TooFewRowsForFolds(k));
+        }
+
+        // shuffle each class and deal its rows to the folds in turn
+        let mut folds: Vec<(Vec<Row>, Vec<Row>)> = vec![(vec![], vec![]); k];
+        let positives_count = positives.len();
+        let (positives, _) = Dataset::draw_rows(&mut rng, positives, positives_count);
+        for (idx, row) in positives.into_iter().enumerate() {
+            folds[idx % k].0.push(row);
+        }
+        let negatives_count = negatives.len();
+        let (negatives, _) = Dataset::draw_rows(&mut rng, negatives, negatives_count);
+        for (idx, row) in negatives.into_iter().enumerate() {
+            folds[idx % k].1.push(row);
+        }
+
+        Ok((0..k)
+            .map(|test_idx| {
+                let mut dataset = Dataset {
+                    learning_pos: vec![],
+                    learning_neg: vec![],
+                    testing_pos: folds[test_idx].0.clone(),
+                    testing_neg: folds[test_idx].1.clone(),
+                };
+                for (fold_idx, (fold_pos, fold_neg)) in folds.iter().enumerate() {
+                    if fold_idx != test_idx {
+                        dataset.learning_pos.extend(fold_pos.iter().cloned());
+                        dataset.learning_neg.extend(fold_neg.iter().cloned());
+                    }
+                }
+                dataset
+            })
+            .collect())
+    }
+
+    // learn from every row of one file and test on every row of another one,
+    // both files must have the same columns, and the numeric columns of the
+    // learning file must stay numeric in the testing one
+    pub fn from_files(
+        learning_path: &str,
+        testing_path: &str,
+        class_column: &str,
+        positive_class: &str,
+    ) -> Result<Dataset, DatasetError> {
+        let (learning_header, learning) =
+            Dataset::read_header_and_rows(learning_path, class_column)?;
+        let (testing_header, testing) = Dataset::read_header_and_rows(testing_path, class_column)?;
+        Dataset::check_classes(&learning, positive_class)?;
+
+        if learning_header != testing_header {
+            return Err(DatasetError::SchemaMismatch(format!(
+                "columns [{}] of '{}' differ from columns [{}] of '{}'",
+                testing_header.join(", "),
+                testing_path,
+                learning_header.join(", "),
+                learning_path
+            )));
+        }
+        let learning_kinds = Dataset::column_kinds(&learning);
+        let testing_kinds = Dataset::column_kinds(&testing);
+        for (attr_idx, attr_name) in learning_header
+            .iter()
+            .filter(|name| *name != class_column)
+            .enumerate()
+        {
+            if let (Some(learning_kind), Some(testing_kind)) =
+                (learning_kinds.get(attr_idx), testing_kinds.get(attr_idx))
+            {
+                // the learning kind is authoritative: numeric looking codes of a
+                // categorical column still match its equality selectors, but text
+                // in a numeric column can never match a threshold
+                if *learning_kind == AttributeKind::Numeric
+                    && *testing_kind == AttributeKind::Categorical
+                {
+                    return Err(DatasetError::SchemaMismatch(format!(
+                        "column '{}' is {} in '{}' but {} in '{}'",
+                        attr_name, learning_kind, learning_path, testing_kind, testing_path
+                    )));
+                }
+            }
+        }
+
+        let (learning_pos, learning_neg): (Vec<Row>, Vec<Row>) = learning
+            .into_iter()
+            .partition(|row| row.class == positive_class);
+        let (testing_pos, testing_neg): (Vec<Row>, Vec<Row>) = testing
+            .into_iter()
+            .partition(|row| row.class == positive_class);
+
+        Ok(Dataset {
+            learning_pos,
+            learning_neg,
+            testing_pos,
+            testing_neg,
+        })
+    }
+
+    // kind of every attribute column: numeric when all its values are numbers,
+    // categorical otherwise, empty when there are no rows
+    pub fn column_kinds(rows: &[Row]) -> Vec<AttributeKind> {
+        let n_attrs = rows.first().map_or(0, |row| row.attributes.len());
+        (0..n_attrs)
+            .map(|attr_idx| {
+                let numeric = rows.iter().all(|row| {
+                    matches!(row.attributes.list[attr_idx], AttributeValuesSet::Num(_, _))
+                });
+                if numeric {
+                    AttributeKind::Numeric
+                } else {
+                    AttributeKind::Categorical
+                }
+            })
+            .collect()
+    }
+
+    // same rows with the positive and negative classes exchanged
+    pub fn swapped(&self) -> Dataset {
+        Dataset {
+            learning_pos: self.learning_neg.clone(),
+            learning_neg: self.learning_pos.clone(),
+            testing_pos: self.testing_neg.clone(),
+            testing_neg: self.testing_pos.clone(),
+        }
+    }
+
+    // name and kind of every attribute of the learning set
+    pub fn schema(&self) -> Vec<(String, AttributeKind)> {
+        let learning = [self.learning_pos.clone(), self.learning_neg.clone()].concat();
+        let kinds = Dataset::column_kinds(&learning);
+
+        learning
+            .first()
+            .map_or(vec![], |row| row.attributes.list.clone())
+            .iter()
+            .zip(kinds)
+            .map(|(set, kind)| (set.name().unwrap_or_default().to_string(), kind))
+            .collect()
+    }
 
-        let class_column_index = headers.iter().position(|x| x == class_column).unwrap();
+    // read every row of a csv file, the class column is kept apart from the attributes
+    pub fn read_rows(path: &str, class_column: &str) -> Result<Vec<Row>, DatasetError> {
+        Ok(Dataset::read_header_and_rows(path, class_column)?.1)
+    }
+
+    // like read_rows, also returns the names of all the columns
+    pub fn read_header_and_rows(
+        path: &str,
+        class_column: &str,
+    ) -> Result<(Vec<String>, Vec<Row>), DatasetError> {
+        let csv_error = |err| DatasetError::from_csv(path, err);
+
+        let mut reader = csv::Reader::from_path(path).map_err(csv_error)?;
+        let headers = reader.headers().map_err(csv_error)?.clone();
+
+        let class_column_index = headers
+            .iter()
+            .position(|x| x == class_column)
+            .ok_or_else(|| DatasetError::MissingClassColumn(class_column.to_string()))?;
 
         let mut all_records = Vec::new();
 
         for result in reader.records() {
-            let record = result.unwrap();
-            let class = record.get(class_column_index).unwrap().to_string();
+            let record = result.map_err(csv_error)?;
+            let class = record[class_column_index].to_string();
 
             let mut row = Row {
                 class: class.clone(),
@@ -62,17 +301,7 @@ impl Dataset {
 
             for (i, field) in record.iter().enumerate() {
                 if i != class_column_index {
-                    let attribute = match field.parse::<f64>() {
-                        Ok(num) => AttributeValuesSet::Num(
-                            headers[i].to_string(),
-                            HashSet::from([OrderedFloat(num)]),
-                        ),
-                        Err(_) => AttributeValuesSet::Cat(
-                            headers[i].to_string(),
-                            HashSet::from([field.to_string()]),
-                        ),
-                    };
-
+                    let attribute = AttributeValuesSet::from_field(&headers[i], field);
                     row.attributes.list.push(attribute);
                 }
             }
@@ -80,35 +309,54 @@ impl Dataset {
             all_records.push(row);
         }
 
+        let header = headers.iter().map(|name| name.to_string()).collect();
+        Ok((header, all_records))
+    }
+
+    fn check_classes(rows: &[Row], positive_class: &str) -> Result<(), DatasetError> {
+        if rows.is_empty() {
+            return Err(DatasetError::EmptyDataset);
+        }
+        if !rows.iter().any(|row| row.class == positive_class) {
+            return Err(DatasetError::NoPositiveExamples(positive_class.to_string()));
+        }
+        if rows.iter().all(|row| row.class == positive_class) {
+            return Err(DatasetError::NoNegativeExamples(positive_class.to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub fn random_split(
+        rng: &mut StdRng,
+        all_records: Vec<Row>,
+        learning_frac: usize,
+    ) -> (Vec<Row>, Vec<Row>) {
         let frac = all_records.len() * learning_frac / 100;
-        let (mut learning_pos, mut learning_neg, mut testing_pos, mut testing_neg) =
-            (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        Dataset::draw_rows(rng, all_records, frac)
+    }
 
-        for _ in 0..frac {
-            let index = rng.gen_range(0..all_records.len());
-            let row = all_records.remove(index);
+    // like the random split, but a non empty stratum always keeps at least one learning row
+    pub fn stratum_split(
+        rng: &mut StdRng,
+        stratum: Vec<Row>,
+        learning_frac: usize,
+    ) -> (Vec<Row>, Vec<Row>) {
+        let minimum = usize::from(!stratum.is_empty() && learning_frac > 0);
+        let frac = (stratum.len() * learning_frac / 100).max(minimum);
+        Dataset::draw_rows(rng, stratum, frac)
+    }
 
-            if row.class == positive_class {
-                learning_pos.push(row);
-            } else {
-                learning_neg.push(row);
-            }
-        }
+    // move `count` random rows out of `rows`, returns the drawn rows and the remaining ones
+    fn draw_rows(rng: &mut StdRng, mut rows: Vec<Row>, count: usize) -> (Vec<Row>, Vec<Row>) {
+        let mut drawn = Vec::new();
 
-        for row in all_records {
-            if row.class == positive_class {
-                testing_pos.push(row);
-            } else {
-                testing_neg.push(row);
-            }
+        for _ in 0..count {
+            let index = rng.gen_range(0..rows.len());
+            drawn.push(rows.remove(index));
         }
 
-        Dataset {
-            learning_pos,
-            learning_neg,
-            testing_pos,
-            testing_neg,
-        }
+        (drawn, rows)
     }
 
     // pub fn get_clause_one_learning_negative(&self, idx: usize) -> AttributeValuesSetList {
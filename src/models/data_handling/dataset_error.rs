@@ -0,0 +1,101 @@
+use std::{error::Error, fmt};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DatasetError {
+    // path of the file that could not be opened
+    MissingFile(String),
+    // the file could be opened but not read as csv
+    InvalidCsv(String),
+    MissingClassColumn(String),
+    // line of the row, number of fields expected and found
+    RaggedRow(u64, u64, u64),
+    EmptyDataset,
+    // the positive class that was not found
+    NoPositiveExamples(String),
+    NoNegativeExamples(String),
+    // the learning percentage that left a class without learning rows
+    NoLearningPositives(usize),
+    NoLearningNegatives(usize),
+    // number of folds that one of the classes cannot fill
+    TooFewRowsForFolds(usize),
+    // the columns of a file do not match the ones expected
+    SchemaMismatch(String),
+    // the only class of a dataset that should have several
+    SingleClass(String),
+    // class and learning percentage that left it without learning rows
+    NoLearningRowsOfClass(String, usize),
+}
+
+impl fmt::Display for DatasetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DatasetError::MissingFile(path) => write!(f, "Dataset file '{}' not found", path),
+            DatasetError::InvalidCsv(reason) => write!(f, "Dataset is not a valid csv: {}", reason),
+            DatasetError::MissingClassColumn(column) => {
+                write!(f, "Class column '{}' not found in the header", column)
+            }
+            DatasetError::RaggedRow(line, expected, found) => write!(
+                f,
+                "Row at line {} has {} fields, expected {}",
+                line, found, expected
+            ),
+            DatasetError::EmptyDataset => write!(f, "Dataset has no rows"),
+            DatasetError::NoPositiveExamples(class) => {
+                write!(f, "Dataset has no rows of the positive class '{}'", class)
+            }
+            DatasetError::NoNegativeExamples(class) => write!(
+                f,
+                "Dataset has no rows outside of the positive class '{}'",
+                class
+            ),
+            DatasetError::NoLearningPositives(frac) => write!(
+                f,
+                "Learning set has no positive rows with a learning fraction of {}%, try a stratified split",
+                frac
+            ),
+            DatasetError::NoLearningNegatives(frac) => write!(
+                f,
+                "Learning set has no negative rows with a learning fraction of {}%, try a stratified split",
+                frac
+            ),
+            DatasetError::TooFewRowsForFolds(k) => write!(
+                f,
+                "Each class needs at least {} rows to be split into {} folds",
+                k, k
+            ),
+            DatasetError::SchemaMismatch(reason) => {
+                write!(f, "Columns do not match: {}", reason)
+            }
+            DatasetError::SingleClass(class) => {
+                write!(f, "Dataset only has rows of the class '{}'", class)
+            }
+            DatasetError::NoLearningRowsOfClass(class, frac) => write!(
+                f,
+                "Learning set has no rows of the class '{}' with a learning fraction of {}%, try a stratified split",
+                class, frac
+            ),
+        }
+    }
+}
+
+impl Error for DatasetError {}
+
+impl DatasetError {
+    pub fn from_csv(path: &str, err: csv::Error) -> DatasetError {
+        match err.kind() {
+            csv::ErrorKind::Io(io_err) if io_err.kind() == std::io::ErrorKind::NotFound => {
+                DatasetError::MissingFile(path.to_string())
+            }
+            csv::ErrorKind::UnequalLengths {
+                pos,
+                expected_len,
+                len,
+            } => DatasetError::RaggedRow(
+                pos.as_ref().map_or(0, |pos| pos.line()),
+                *expected_len,
+                *len,
+            ),
+            _ => DatasetError::InvalidCsv(err.to_string()),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+// fixed size set of small value ids, one bit per id of an attribute dictionary
+#[derive(Clone, Debug, Default)]
+pub struct ValueBitSet {
+    blocks: Vec<u64>,
+}
+
+// sets with the same ids are equal, whatever the number of blocks
+impl PartialEq for ValueBitSet {
+    fn eq(&self, other: &Self) -> bool {
+        let (longer, shorter) = if self.blocks.len() >= other.blocks.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        longer.blocks[..shorter.blocks.len()] == shorter.blocks[..]
+            && longer.blocks[shorter.blocks.len()..]
+                .iter()
+                .all(|block| *block == 0)
+    }
+}
+
+impl Eq for ValueBitSet {}
+
+impl ValueBitSet {
+    // empty set able to hold the ids 0..n_values
+    pub fn new(n_values: usize) -> ValueBitSet {
+        ValueBitSet {
+            blocks: vec![0; n_values.div_ceil(64)],
+        }
+    }
+
+    pub fn insert(&mut self, id: usize) {
+        let block = id / 64;
+        if block >= self.blocks.len() {
+            self.blocks.resize(block + 1, 0);
+        }
+        self.blocks[block] |= 1 << (id % 64);
+    }
+
+    pub fn contains(&self, id: usize) -> bool {
+        self.blocks
+            .get(id / 64)
+            .is_some_and(|block| block & (1 << (id % 64)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks
+            .iter()
+            .map(|block| block.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.iter().all(|block| *block == 0)
+    }
+
+    // ids in the set, in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.blocks.iter().enumerate().flat_map(|(idx, block)| {
+            (0..64)
+                .filter(move |bit| block & (1 << bit) != 0)
+                .map(move |bit| idx * 64 + bit)
+        })
+    }
+
+    pub fn union(&self, other: &ValueBitSet) -> ValueBitSet {
+        let (longer, shorter) = if self.blocks.len() >= other.blocks.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut result = longer.clone();
+        for (block, other_block) in result.blocks.iter_mut().zip(&shorter.blocks) {
+            *block |= other_block;
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &ValueBitSet) -> ValueBitSet {
+        ValueBitSet {
+            blocks: self
+                .blocks
+                .iter()
+                .zip(&other.blocks)
+                .map(|(block, other_block)| block & other_block)
+                .collect(),
+        }
+    }
+
+    pub fn difference(&self, other: &ValueBitSet) -> ValueBitSet {
+        let mut result = self.clone();
+        for (block, other_block) in result.blocks.iter_mut().zip(&other.blocks) {
+            *block &= !other_block;
+        }
+        result
+    }
+}
+
+// dictionary encoded counterpart of an AttributeValuesSetList: one bitset of
+// value ids per attribute, see ValueDictionary to go back and forth
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EncodedValuesSetList {
+    pub sets: Vec<ValueBitSet>,
+}
+
+impl EncodedValuesSetList {
+    pub fn from_vec(sets: Vec<ValueBitSet>) -> Self {
+        EncodedValuesSetList { sets }
+    }
+
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+
+    // number of values in all the sets of the list
+    pub fn n_values(&self) -> usize {
+        self.sets.iter().map(ValueBitSet::len).sum()
+    }
+
+    // true when at least one attribute keeps a value
+    pub fn has_values(&self) -> bool {
+        self.sets.iter().any(|set| !set.is_empty())
+    }
+
+    pub fn union(&self, other: &EncodedValuesSetList) -> EncodedValuesSetList {
+        self.combine(other, ValueBitSet::union)
+    }
+
+    pub fn intersection(&self, other: &EncodedValuesSetList) -> EncodedValuesSetList {
+        self.combine(other, ValueBitSet::intersection)
+    }
+
+    pub fn difference(&self, other: &EncodedValuesSetList) -> EncodedValuesSetList {
+        self.combine(other, ValueBitSet::difference)
+    }
+
+    // an attribute missing from one of the lists is taken as an empty set
+    fn combine(
+        &self,
+        other: &EncodedValuesSetList,
+        operation: fn(&ValueBitSet, &ValueBitSet) -> ValueBitSet,
+    ) -> EncodedValuesSetList {
+        let empty = ValueBitSet::default();
+        EncodedValuesSetList {
+            sets: (0..self.len().max(other.len()))
+                .map(|idx| {
+                    operation(
+                        self.sets.get(idx).unwrap_or(&empty),
+                        other.sets.get(idx).unwrap_or(&empty),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
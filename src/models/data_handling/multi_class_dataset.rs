@@ -0,0 +1,130 @@
+use rand::rngs::StdRng;
+
+use super::{
+    dataset::{Dataset, SplitMethod},
+    dataset_error::DatasetError,
+    row::Row,
+};
+
+// rows of every class split once into a learning and a testing set, the
+// binary datasets of the one-vs-rest and one-vs-one models are taken from it
+#[derive(Debug)]
+pub struct MultiClassDataset {
+    // every class of the file, sorted by name
+    pub classes: Vec<String>,
+    pub learning: Vec<Row>,
+    pub testing: Vec<Row>,
+}
+
+impl MultiClassDataset {
+    pub fn with_split(
+        mut rng: StdRng,
+        path: &str,
+        class_column: &str,
+        learning_frac: usize,
+        split: SplitMethod,
+    ) -> Result<MultiClassDataset, DatasetError> {
+        let all_records = Dataset::read_rows(path, class_column)?;
+        let mut classes = all_records
+            .iter()
+            .map(|row| row.class.clone())
+            .collect::<Vec<String>>();
+        classes.sort_unstable();
+        classes.dedup();
+        match classes.as_slice() {
+            [] => return Err(DatasetError::EmptyDataset),
+            [class] => return Err(DatasetError::SingleClass(class.clone())),
+            _ => {}
+        }
+
+        let (learning, testing) = match split {
+            SplitMethod::Random => Dataset::random_split(&mut rng, all_records, learning_frac),
+            SplitMethod::Stratified => {
+                let mut strata = vec![vec![]; classes.len()];
+                for row in all_records {
+                    let class_idx = classes.binary_search(&row.class).unwrap();
+                    strata[class_idx].push(row);
+                }
+                let (mut learning, mut testing) = (vec![], vec![]);
+                for stratum in strata {
+                    let (learning_rows, testing_rows) =
+                        Dataset::stratum_split(&mut rng, stratum, learning_frac);
+                    learning.extend(learning_rows);
+                    testing.extend(testing_rows);
+                }
+                (learning, testing)
+            }
+        };
+
+        // every class needs learning rows to get a model
+        for class in &classes {
+            if !learning.iter().any(|row| &row.class == class) {
+                return Err(DatasetError::NoLearningRowsOfClass(
+                    class.clone(),
+                    learning_frac,
+                ));
+            }
+        }
+
+        Ok(MultiClassDataset {
+            classes,
+            learning,
+            testing,
+        })
+    }
+
+    // number of learning rows of every class, in the order of the classes
+    pub fn learning_counts(&self) -> Vec<usize> {
+        self.classes
+            .iter()
+            .map(|class| {
+                self.learning
+                    .iter()
+                    .filter(|row| &row.class == class)
+                    .count()
+            })
+            .collect()
+    }
+
+    // the class against the rows of every other class
+    pub fn one_vs_rest(&self, class: &str) -> Dataset {
+        self.binary(|row_class| row_class == class, |_| true)
+    }
+
+    // the positive class against the negative one, the rows of other classes are left out
+    pub fn one_vs_one(&self, positive: &str, negative: &str) -> Dataset {
+        self.binary(
+            |row_class| row_class == positive,
+            |row_class| row_class == negative,
+        )
+    }
+
+    fn binary<P, N>(&self, is_positive: P, is_negative: N) -> Dataset
+    where
+        P: Fn(&str) -> bool,
+        N: Fn(&str) -> bool,
+    {
+        let split = |rows: &[Row]| {
+            let positives = rows
+                .iter()
+                .filter(|row| is_positive(&row.class))
+                .cloned()
+                .collect::<Vec<Row>>();
+            let negatives = rows
+                .iter()
+                .filter(|row| !is_positive(&row.class) && is_negative(&row.class))
+                .cloned()
+                .collect::<Vec<Row>>();
+            (positives, negatives)
+        };
+        let (learning_pos, learning_neg) = split(&self.learning);
+        let (testing_pos, testing_neg) = split(&self.testing);
+
+        Dataset {
+            learning_pos,
+            learning_neg,
+            testing_pos,
+            testing_neg,
+        }
+    }
+}
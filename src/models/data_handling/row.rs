@@ -1,6 +1,6 @@
 use std::fmt;
 
-use super::{attribute_value::AttrValue, attribute_values_set::AttributeValuesSetList};
+use super::attribute_values_set::{AttributeValuesSet, AttributeValuesSetList};
 
 #[derive(Clone, Debug)]
 pub struct Row {
@@ -14,3 +14,13 @@ impl fmt::Display for Row {
         Ok(())
     }
 }
+
+impl Row {
+    // get the value set of an attribute by its name
+    pub fn get_attribute(&self, attr_name: &str) -> Option<&AttributeValuesSet> {
+        self.attributes
+            .list
+            .iter()
+            .find(|set| set.name() == Some(attr_name))
+    }
+}
@@ -1,14 +1,39 @@
 // tests
 #[cfg(test)]
 mod tests {
-    use crate::models::data_handling::dataset::Dataset;
+    use std::{collections::HashSet, env, fs};
 
+    use crate::models::data_handling::{
+        attribute_values_set::{AttributeValuesSet, AttributeValuesSetList},
+        dataset::{Dataset, SplitMethod},
+        dataset_error::DatasetError,
+        encoded_values_set::ValueBitSet,
+        multi_class_dataset::MultiClassDataset,
+        row::Row,
+        value_dictionary::ValueDictionary,
+    };
+
+    use ordered_float::OrderedFloat;
     use rand::{rngs::StdRng, SeedableRng};
 
+    fn write_csv(name: &str, content: &str) -> String {
+        let path = env::temp_dir().join(format!("oqat_{}_{}.csv", name, std::process::id()));
+        fs::write(&path, content).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    fn load_csv(name: &str, content: &str, positive_class: &str) -> Result<Dataset, DatasetError> {
+        let path = write_csv(name, content);
+        let rng = StdRng::seed_from_u64(1000);
+        let dataset = Dataset::new(rng, &path, "class", positive_class, 80);
+        fs::remove_file(&path).unwrap();
+        dataset
+    }
+
     #[test]
     fn test_new() {
         let rng = StdRng::seed_from_u64(1000);
-        let dataset = Dataset::new(rng, "datasets/test1.csv", "class", "yes", 80);
+        let dataset = Dataset::new(rng, "datasets/test1.csv", "class", "yes", 80).unwrap();
 
         let len = dataset.learning_pos.len()
             + dataset.learning_neg.len()
@@ -21,4 +46,291 @@ mod tests {
         assert_eq!(dataset.testing_pos.len(), 1);
         assert_eq!(dataset.testing_neg.len(), 1);
     }
+
+    #[test]
+    fn test_new_errors() {
+        let rng = StdRng::seed_from_u64(1000);
+        let err = Dataset::new(rng, "datasets/missing.csv", "class", "yes", 80).unwrap_err();
+        assert_eq!(
+            err,
+            DatasetError::MissingFile("datasets/missing.csv".to_string())
+        );
+
+        let rng = StdRng::seed_from_u64(1000);
+        let err = Dataset::new(rng, "datasets/test1.csv", "label", "yes", 80).unwrap_err();
+        assert_eq!(err, DatasetError::MissingClassColumn("label".to_string()));
+
+        let err = load_csv("ragged", "a,b,class\n1,2,yes\n1,no\n", "yes").unwrap_err();
+        assert_eq!(err, DatasetError::RaggedRow(3, 3, 2));
+
+        let err = load_csv("empty", "a,b,class\n", "yes").unwrap_err();
+        assert_eq!(err, DatasetError::EmptyDataset);
+
+        let err = load_csv("no_pos", "a,class\n1,no\n2,no\n", "yes").unwrap_err();
+        assert_eq!(err, DatasetError::NoPositiveExamples("yes".to_string()));
+
+        let err = load_csv("no_neg", "a,class\n1,yes\n2,yes\n", "yes").unwrap_err();
+        assert_eq!(err, DatasetError::NoNegativeExamples("yes".to_string()));
+    }
+
+    #[test]
+    fn test_stratified_split() {
+        let rng = StdRng::seed_from_u64(7);
+        let dataset = Dataset::with_split(
+            rng,
+            "datasets/test2.csv",
+            "class",
+            "perfect",
+            50,
+            SplitMethod::Stratified,
+        )
+        .unwrap();
+
+        // 13 positive and 14 negative rows, half of each class is used for learning
+        assert_eq!(dataset.learning_pos.len(), 6);
+        assert_eq!(dataset.testing_pos.len(), 7);
+        assert_eq!(dataset.learning_neg.len(), 7);
+        assert_eq!(dataset.testing_neg.len(), 7);
+        assert!(dataset
+            .learning_pos
+            .iter()
+            .all(|row| row.class == "perfect"));
+        assert!(dataset
+            .learning_neg
+            .iter()
+            .all(|row| row.class != "perfect"));
+
+        // a tiny learning fraction still keeps one row of each class
+        let rng = StdRng::seed_from_u64(7);
+        let dataset = Dataset::with_split(
+            rng,
+            "datasets/test1.csv",
+            "class",
+            "yes",
+            1,
+            SplitMethod::Stratified,
+        )
+        .unwrap();
+        assert_eq!(dataset.learning_pos.len(), 1);
+        assert_eq!(dataset.learning_neg.len(), 1);
+
+        let rng = StdRng::seed_from_u64(7);
+        let err = Dataset::new(rng, "datasets/test1.csv", "class", "yes", 1).unwrap_err();
+        assert_eq!(err, DatasetError::NoLearningPositives(1));
+    }
+
+    #[test]
+    fn test_k_folds() {
+        let rng = StdRng::seed_from_u64(1000);
+        let folds = Dataset::k_folds(rng, "datasets/test1.csv", "class", "yes", 3).unwrap();
+
+        assert_eq!(folds.len(), 3);
+        let mut testing_pos = 0;
+        let mut testing_neg = 0;
+        for fold in &folds {
+            assert_eq!(fold.learning_pos.len() + fold.testing_pos.len(), 4);
+            assert_eq!(fold.learning_neg.len() + fold.testing_neg.len(), 5);
+            assert!(!fold.testing_pos.is_empty());
+            assert!(!fold.testing_neg.is_empty());
+            testing_pos += fold.testing_pos.len();
+            testing_neg += fold.testing_neg.len();
+        }
+        // every row is tested exactly once
+        assert_eq!(testing_pos, 4);
+        assert_eq!(testing_neg, 5);
+
+        let rng = StdRng::seed_from_u64(1000);
+        let err = Dataset::k_folds(rng, "datasets/test1.csv", "class", "yes", 5).unwrap_err();
+        assert_eq!(err, DatasetError::TooFewRowsForFolds(5));
+    }
+
+    #[test]
+    fn test_from_files() {
+        let testing = write_csv(
+            "testing",
+            "size,color,class\nsmall,red,yes\nlarge,blue,no\n",
+        );
+        let dataset = Dataset::from_files("datasets/test1.csv", &testing, "class", "yes").unwrap();
+        assert_eq!(dataset.learning_pos.len(), 4);
+        assert_eq!(dataset.learning_neg.len(), 5);
+        assert_eq!(dataset.testing_pos.len(), 1);
+        assert_eq!(dataset.testing_neg.len(), 1);
+
+        let renamed = write_csv("renamed", "size,colour,class\nsmall,red,yes\n");
+        let err = Dataset::from_files("datasets/test1.csv", &renamed, "class", "yes").unwrap_err();
+        assert!(matches!(err, DatasetError::SchemaMismatch(_)));
+
+        // a categorical column whose testing rows only have numeric looking codes
+        let coded = write_csv("coded", "code,class\n1,yes\n2,no\nx,no\n");
+        let coded_testing = write_csv("coded_testing", "code,class\n1,yes\n2,no\n");
+        let dataset = Dataset::from_files(&coded, &coded_testing, "class", "yes").unwrap();
+        assert_eq!(dataset.testing_pos.len(), 1);
+        assert_eq!(dataset.testing_neg.len(), 1);
+
+        let retyped = write_csv("retyped", "code,class\n1,yes\nsmall,no\n");
+        let err = Dataset::from_files(&coded_testing, &retyped, "class", "yes").unwrap_err();
+        assert_eq!(
+            err,
+            DatasetError::SchemaMismatch(format!(
+                "column 'code' is numeric in '{}' but categorical in '{}'",
+                coded_testing, retyped
+            ))
+        );
+
+        for path in [testing, renamed, coded, coded_testing, retyped] {
+            fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_value_bitset() {
+        let mut a = ValueBitSet::new(100);
+        for id in [1, 5, 70] {
+            a.insert(id);
+        }
+        let mut b = ValueBitSet::new(10);
+        for id in [5, 6] {
+            b.insert(id);
+        }
+
+        assert_eq!(a.len(), 3);
+        assert!(a.contains(70) && !a.contains(6) && !b.contains(70));
+        assert_eq!(a.union(&b).iter().collect::<Vec<_>>(), vec![1, 5, 6, 70]);
+        assert_eq!(b.union(&a), a.union(&b));
+        assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), vec![5]);
+        assert_eq!(a.difference(&b).iter().collect::<Vec<_>>(), vec![1, 70]);
+        assert!(b.difference(&a).contains(6));
+        assert!(ValueBitSet::new(200) == ValueBitSet::default());
+        assert!(ValueBitSet::new(200).is_empty());
+    }
+
+    fn values_set_list(size: &[&str], weight: &[f64]) -> AttributeValuesSetList {
+        AttributeValuesSetList::from_vec(vec![
+            AttributeValuesSet::Cat(
+                "size".to_string(),
+                size.iter().map(|value| value.to_string()).collect(),
+            ),
+            AttributeValuesSet::Num(
+                "weight".to_string(),
+                weight.iter().map(|value| OrderedFloat(*value)).collect(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_value_dictionary() {
+        let rng = StdRng::seed_from_u64(1000);
+        let dataset = Dataset::new(rng, "datasets/test1.csv", "class", "yes", 80).unwrap();
+        let rows = dataset.learning_pos.iter().chain(&dataset.learning_neg);
+        let dictionary = ValueDictionary::from_rows(rows.clone());
+
+        assert_eq!(dictionary.attributes.len(), 2);
+        assert_eq!(dictionary.attributes[0].name, "size");
+        for row in rows {
+            let encoded = dictionary.encode(&row.attributes);
+            assert_eq!(encoded.n_values(), 2);
+            assert_eq!(dictionary.decode(&encoded), row.attributes);
+        }
+    }
+
+    #[test]
+    fn test_encoded_set_operations() {
+        let a = values_set_list(&["small", "large"], &[1.0, 2.5]);
+        let b = values_set_list(&["large", "medium"], &[2.5, 4.0]);
+        let dictionary = ValueDictionary::from_rows(&[
+            Row {
+                class: "x".to_string(),
+                attributes: a.clone(),
+            },
+            Row {
+                class: "y".to_string(),
+                attributes: b.clone(),
+            },
+        ]);
+        let (encoded_a, encoded_b) = (dictionary.encode(&a), dictionary.encode(&b));
+
+        // the encoded operations give the same sets as the plain ones
+        assert_eq!(dictionary.decode(&encoded_a.union(&encoded_b)), a.union(&b));
+        assert_eq!(
+            dictionary.decode(&encoded_a.intersection(&encoded_b)),
+            a.intersection(&b)
+        );
+        assert_eq!(
+            dictionary.decode(&encoded_a.difference(&encoded_b)),
+            a.difference(&b)
+        );
+        assert_eq!(
+            dictionary
+                .decode(&encoded_a.intersection(&encoded_b))
+                .to_string(),
+            a.intersection(&b).to_string()
+        );
+
+        // values the dictionary has never seen are left out
+        let unseen = values_set_list(&["huge"], &[2.5]);
+        let encoded_unseen = dictionary.encode(&unseen);
+        assert_eq!(encoded_unseen.n_values(), 1);
+        assert_eq!(
+            dictionary.decode(&encoded_unseen).list[0],
+            AttributeValuesSet::Cat("size".to_string(), HashSet::new())
+        );
+    }
+
+    #[test]
+    fn test_multi_class_dataset() {
+        let rng = StdRng::seed_from_u64(1000);
+        let dataset = MultiClassDataset::with_split(
+            rng,
+            "datasets/hayes_roth.csv",
+            "class",
+            80,
+            SplitMethod::Stratified,
+        )
+        .unwrap();
+
+        assert_eq!(dataset.classes, vec!["1", "2", "3"]);
+        let counts = dataset.learning_counts();
+        assert_eq!(counts.iter().sum::<usize>(), dataset.learning.len());
+        assert!(counts.iter().all(|count| *count > 0));
+
+        let rest = dataset.one_vs_rest("2");
+        assert_eq!(rest.learning_pos.len(), counts[1]);
+        assert_eq!(rest.learning_neg.len(), counts[0] + counts[2]);
+        assert_eq!(
+            rest.testing_pos.len() + rest.testing_neg.len(),
+            dataset.testing.len()
+        );
+        assert!(rest.learning_pos.iter().all(|row| row.class == "2"));
+        assert!(rest.learning_neg.iter().all(|row| row.class != "2"));
+
+        let pair = dataset.one_vs_one("3", "1");
+        assert_eq!(pair.learning_pos.len(), counts[2]);
+        assert_eq!(pair.learning_neg.len(), counts[0]);
+        assert!(pair.testing_pos.iter().all(|row| row.class == "3"));
+        assert!(pair.testing_neg.iter().all(|row| row.class == "1"));
+
+        let path = write_csv("single_class", "x,class\n1,a\n2,a\n");
+        let err = MultiClassDataset::with_split(
+            StdRng::seed_from_u64(1000),
+            &path,
+            "class",
+            80,
+            SplitMethod::Random,
+        )
+        .unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(err, DatasetError::SingleClass("a".to_string()));
+
+        let path = write_csv("rare_class", "x,class\n1,a\n2,a\n3,a\n4,b\n");
+        let err = MultiClassDataset::with_split(
+            StdRng::seed_from_u64(1000),
+            &path,
+            "class",
+            1,
+            SplitMethod::Random,
+        )
+        .unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(err, DatasetError::NoLearningRowsOfClass(_, 1)));
+    }
 }
@@ -0,0 +1,148 @@
+use std::collections::{HashMap, HashSet};
+
+use ordered_float::OrderedFloat;
+
+use super::{
+    attribute_values_set::{AttributeValuesSet, AttributeValuesSetList},
+    encoded_values_set::{EncodedValuesSetList, ValueBitSet},
+    row::Row,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DictionaryValue {
+    Num(OrderedFloat<f64>),
+    Cat(String),
+}
+
+// distinct values of one attribute, the id of a value is its index in values
+#[derive(Clone, Debug, Default)]
+pub struct AttributeDictionary {
+    pub name: String,
+    pub values: Vec<DictionaryValue>,
+    ids: HashMap<DictionaryValue, usize>,
+}
+
+impl AttributeDictionary {
+    pub fn id(&self, value: &DictionaryValue) -> Option<usize> {
+        self.ids.get(value).copied()
+    }
+
+    fn intern(&mut self, value: DictionaryValue) {
+        if !self.ids.contains_key(&value) {
+            self.ids.insert(value.clone(), self.values.len());
+            self.values.push(value);
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        !self.values.is_empty()
+            && self
+                .values
+                .iter()
+                .all(|value| matches!(value, DictionaryValue::Num(_)))
+    }
+}
+
+// maps the values of every attribute to small ids so value sets can be stored as bitsets
+#[derive(Clone, Debug, Default)]
+pub struct ValueDictionary {
+    pub attributes: Vec<AttributeDictionary>,
+}
+
+impl ValueDictionary {
+    // ids are given in order of appearance, so the same rows always give the same ids
+    pub fn from_rows<'a, I: IntoIterator<Item = &'a Row>>(rows: I) -> ValueDictionary {
+        let mut dictionary = ValueDictionary::default();
+        for row in rows {
+            for (attr_idx, set) in row.attributes.list.iter().enumerate() {
+                if attr_idx == dictionary.attributes.len() {
+                    dictionary.attributes.push(AttributeDictionary::default());
+                }
+                let attribute = &mut dictionary.attributes[attr_idx];
+                if let Some(name) = set.name() {
+                    if attribute.name.is_empty() {
+                        attribute.name = name.to_string();
+                    }
+                }
+                for value in Self::set_values(set) {
+                    attribute.intern(value);
+                }
+            }
+        }
+
+        dictionary
+    }
+
+    // values of a set in a fixed order, whatever the order of the hash set
+    fn set_values(set: &AttributeValuesSet) -> Vec<DictionaryValue> {
+        let mut values = match set {
+            AttributeValuesSet::Num(_, values) => {
+                values.iter().map(|v| DictionaryValue::Num(*v)).collect()
+            }
+            AttributeValuesSet::Cat(_, values) => values
+                .iter()
+                .map(|v| DictionaryValue::Cat(v.clone()))
+                .collect(),
+            AttributeValuesSet::Empty => vec![],
+        };
+        values.sort_unstable();
+        values
+    }
+
+    // values missing from the dictionary are left out
+    pub fn encode(&self, list: &AttributeValuesSetList) -> EncodedValuesSetList {
+        let sets = list
+            .list
+            .iter()
+            .enumerate()
+            .map(|(attr_idx, set)| match self.attributes.get(attr_idx) {
+                Some(attribute) => {
+                    let mut bitset = ValueBitSet::new(attribute.values.len());
+                    for value in Self::set_values(set) {
+                        if let Some(id) = attribute.id(&value) {
+                            bitset.insert(id);
+                        }
+                    }
+                    bitset
+                }
+                None => ValueBitSet::default(),
+            })
+            .collect();
+
+        EncodedValuesSetList::from_vec(sets)
+    }
+
+    // numeric attributes give Num sets, any other attribute gives Cat sets
+    pub fn decode(&self, encoded: &EncodedValuesSetList) -> AttributeValuesSetList {
+        let list = encoded
+            .sets
+            .iter()
+            .enumerate()
+            .map(|(attr_idx, bitset)| match self.attributes.get(attr_idx) {
+                Some(attribute) if attribute.is_numeric() => {
+                    let values: HashSet<OrderedFloat<f64>> = bitset
+                        .iter()
+                        .filter_map(|id| match &attribute.values[id] {
+                            DictionaryValue::Num(value) => Some(*value),
+                            DictionaryValue::Cat(_) => None,
+                        })
+                        .collect();
+                    AttributeValuesSet::Num(attribute.name.clone(), values)
+                }
+                Some(attribute) => {
+                    let values: HashSet<String> = bitset
+                        .iter()
+                        .map(|id| match &attribute.values[id] {
+                            DictionaryValue::Num(value) => value.to_string(),
+                            DictionaryValue::Cat(value) => value.clone(),
+                        })
+                        .collect();
+                    AttributeValuesSet::Cat(attribute.name.clone(), values)
+                }
+                None => AttributeValuesSet::Empty,
+            })
+            .collect();
+
+        AttributeValuesSetList::from_vec(list)
+    }
+}
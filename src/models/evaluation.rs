@@ -0,0 +1,4 @@
+pub mod confusion_matrix;
+pub mod cross_validation;
+pub mod multi_class_confusion_matrix;
+pub mod unit_tests;
@@ -0,0 +1,112 @@
+use std::fmt;
+
+use crate::models::{
+    classifier::rule_model::RuleModel,
+    data_handling::{dataset::Dataset, row::Row},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConfusionMatrix {
+    pub true_pos: usize,
+    pub false_pos: usize,
+    pub true_neg: usize,
+    pub false_neg: usize,
+}
+
+impl fmt::Display for ConfusionMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Confusion matrix:")?;
+        writeln!(f, "              pred +  pred -")?;
+        writeln!(f, "  actual +  {:>7} {:>7}", self.true_pos, self.false_neg)?;
+        writeln!(f, "  actual -  {:>7} {:>7}", self.false_pos, self.true_neg)?;
+        writeln!(f, "Accuracy: {:.4}", self.accuracy())?;
+        writeln!(f, "Precision: {:.4}", self.precision())?;
+        writeln!(f, "Recall: {:.4}", self.recall())?;
+        writeln!(f, "F1: {:.4}", self.f1())?;
+        writeln!(f, "Specificity: {:.4}", self.specificity())?;
+        write!(f, "Balanced accuracy: {:.4}", self.balanced_accuracy())
+    }
+}
+
+impl ConfusionMatrix {
+    pub fn new() -> ConfusionMatrix {
+        ConfusionMatrix::default()
+    }
+
+    // classify every positive and negative row and count the outcomes
+    pub fn from_predictions<F>(predict: F, positives: &[Row], negatives: &[Row]) -> ConfusionMatrix
+    where
+        F: Fn(&Row) -> bool,
+    {
+        let mut matrix = ConfusionMatrix::new();
+
+        for row in positives {
+            matrix.add(true, predict(row));
+        }
+        for row in negatives {
+            matrix.add(false, predict(row));
+        }
+
+        matrix
+    }
+
+    // evaluate a model on the testing split of the dataset
+    pub fn from_testing_set(model: &RuleModel, dataset: &Dataset) -> ConfusionMatrix {
+        ConfusionMatrix::from_predictions(
+            |row| model.predict(row),
+            &dataset.testing_pos,
+            &dataset.testing_neg,
+        )
+    }
+
+    pub fn add(&mut self, actual: bool, predicted: bool) {
+        match (actual, predicted) {
+            (true, true) => self.true_pos += 1,
+            (true, false) => self.false_neg += 1,
+            (false, true) => self.false_pos += 1,
+            (false, false) => self.true_neg += 1,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.true_pos + self.false_pos + self.true_neg + self.false_neg
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        ratio(self.true_pos + self.true_neg, self.total())
+    }
+
+    pub fn precision(&self) -> f64 {
+        ratio(self.true_pos, self.true_pos + self.false_pos)
+    }
+
+    pub fn recall(&self) -> f64 {
+        ratio(self.true_pos, self.true_pos + self.false_neg)
+    }
+
+    pub fn specificity(&self) -> f64 {
+        ratio(self.true_neg, self.true_neg + self.false_pos)
+    }
+
+    pub fn f1(&self) -> f64 {
+        let (precision, recall) = (self.precision(), self.recall());
+        if precision + recall == 0.0 {
+            0.0
+        } else {
+            2.0 * precision * recall / (precision + recall)
+        }
+    }
+
+    pub fn balanced_accuracy(&self) -> f64 {
+        (self.recall() + self.specificity()) / 2.0
+    }
+}
+
+// metrics with an empty denominator are reported as 0
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
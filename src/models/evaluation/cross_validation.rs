@@ -0,0 +1,118 @@
+use std::fmt;
+
+use rand::rngs::StdRng;
+
+use crate::models::{
+    classifier::{
+        rule_model::RuleForm,
+        training::{train_model, TrainingOptions},
+    },
+    config::Algorithm,
+    data_handling::dataset::Dataset,
+};
+
+use super::confusion_matrix::ConfusionMatrix;
+
+type FoldMetric = fn(&FoldResult) -> f64;
+
+#[derive(Clone, Debug)]
+pub struct FoldResult {
+    pub evaluation: ConfusionMatrix,
+    pub n_clauses: usize,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CrossValidationReport {
+    pub folds: Vec<FoldResult>,
+}
+
+impl fmt::Display for CrossValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Fold  Clauses  Accuracy  Precision  Recall  F1      Specificity  Balanced acc"
+        )?;
+        for (idx, fold) in self.folds.iter().enumerate() {
+            let e = &fold.evaluation;
+            writeln!(
+                f,
+                "{:<5} {:<8} {:<9.4} {:<10.4} {:<7.4} {:<7.4} {:<12.4} {:.4}",
+                idx,
+                fold.n_clauses,
+                e.accuracy(),
+                e.precision(),
+                e.recall(),
+                e.f1(),
+                e.specificity(),
+                e.balanced_accuracy()
+            )?;
+        }
+
+        let metrics: [(&str, FoldMetric); 7] = [
+            ("Clauses", |fold| fold.n_clauses as f64),
+            ("Accuracy", |fold| fold.evaluation.accuracy()),
+            ("Precision", |fold| fold.evaluation.precision()),
+            ("Recall", |fold| fold.evaluation.recall()),
+            ("F1", |fold| fold.evaluation.f1()),
+            ("Specificity", |fold| fold.evaluation.specificity()),
+            ("Balanced accuracy", |fold| {
+                fold.evaluation.balanced_accuracy()
+            }),
+        ];
+        for (name, metric) in metrics {
+            let (mean, std) = self.mean_std(metric);
+            writeln!(f, "{}: {:.4} ± {:.4}", name, mean, std)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl CrossValidationReport {
+    // mean and sample standard deviation of a metric across the folds
+    pub fn mean_std(&self, metric: impl Fn(&FoldResult) -> f64) -> (f64, f64) {
+        let values = self.folds.iter().map(metric).collect::<Vec<f64>>();
+        if values.is_empty() {
+            return (0.0, 0.0);
+        }
+
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        if values.len() < 2 {
+            return (mean, 0.0);
+        }
+        let variance =
+            values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+
+        (mean, variance.sqrt())
+    }
+}
+
+// train the full pipeline on the learning part of every fold and evaluate it on the testing part,
+// there is one report per learned form, the clauses of a DNF model are its terms
+pub fn cross_validate(
+    rng: StdRng,
+    folds: &[Dataset],
+    algorithm: &Algorithm,
+    training: &TrainingOptions,
+) -> Vec<(RuleForm, CrossValidationReport)> {
+    let mut reports = training
+        .forms
+        .iter()
+        .map(|form| (*form, CrossValidationReport::default()))
+        .collect::<Vec<(RuleForm, CrossValidationReport)>>();
+
+    for (idx, fold) in folds.iter().enumerate() {
+        println!("Fold {}", idx);
+        for (form, report) in &mut reports {
+            let model = train_model(rng.clone(), fold, algorithm, training, *form);
+            println!("{} model: |{}| {}", form, model.len(), model);
+
+            report.folds.push(FoldResult {
+                evaluation: ConfusionMatrix::from_testing_set(&model, fold),
+                n_clauses: model.len(),
+            });
+        }
+    }
+
+    reports
+}
@@ -0,0 +1,151 @@
+use std::fmt;
+
+use crate::models::{
+    classifier::multi_class::MultiClassModel,
+    data_handling::{multi_class_dataset::MultiClassDataset, row::Row},
+};
+
+use super::confusion_matrix::ConfusionMatrix;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MultiClassConfusionMatrix {
+    pub classes: Vec<String>,
+    // counts[actual][predicted], in the order of the classes
+    pub counts: Vec<Vec<usize>>,
+}
+
+impl fmt::Display for MultiClassConfusionMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self
+            .classes
+            .iter()
+            .map(|class| class.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(7);
+
+        writeln!(f, "Confusion matrix (rows: actual, columns: predicted):")?;
+        write!(f, "  {:<width$}", "", width = width)?;
+        for class in &self.classes {
+            write!(f, " {:>width$}", class, width = width)?;
+        }
+        writeln!(f)?;
+        for (class, counts) in self.classes.iter().zip(&self.counts) {
+            write!(f, "  {:<width$}", class, width = width)?;
+            for count in counts {
+                write!(f, " {:>width$}", count, width = width)?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(
+            f,
+            "  {:<width$} Precision  Recall  F1",
+            "Class",
+            width = width
+        )?;
+        for (class_idx, class) in self.classes.iter().enumerate() {
+            let e = self.one_vs_rest(class_idx);
+            writeln!(
+                f,
+                "  {:<width$} {:<10.4} {:<7.4} {:.4}",
+                class,
+                e.precision(),
+                e.recall(),
+                e.f1(),
+                width = width
+            )?;
+        }
+        writeln!(f, "Accuracy: {:.4}", self.accuracy())?;
+        write!(f, "Macro F1: {:.4}", self.macro_f1())
+    }
+}
+
+impl MultiClassConfusionMatrix {
+    pub fn new(classes: Vec<String>) -> MultiClassConfusionMatrix {
+        let n_classes = classes.len();
+        MultiClassConfusionMatrix {
+            classes,
+            counts: vec![vec![0; n_classes]; n_classes],
+        }
+    }
+
+    // classify every row and count the outcomes, rows of unknown classes are skipped
+    pub fn from_predictions<'a, F>(
+        predict: F,
+        classes: Vec<String>,
+        rows: &[Row],
+    ) -> MultiClassConfusionMatrix
+    where
+        F: Fn(&Row) -> &'a str,
+    {
+        let mut matrix = MultiClassConfusionMatrix::new(classes);
+
+        for row in rows {
+            matrix.add(&row.class, predict(row));
+        }
+
+        matrix
+    }
+
+    // evaluate a model on the testing split of the dataset
+    pub fn from_testing_set(
+        model: &MultiClassModel,
+        dataset: &MultiClassDataset,
+    ) -> MultiClassConfusionMatrix {
+        MultiClassConfusionMatrix::from_predictions(
+            |row| model.predict(row),
+            dataset.classes.clone(),
+            &dataset.testing,
+        )
+    }
+
+    pub fn add(&mut self, actual: &str, predicted: &str) {
+        let index = |class: &str| self.classes.iter().position(|name| name == class);
+        if let (Some(actual), Some(predicted)) = (index(actual), index(predicted)) {
+            self.counts[actual][predicted] += 1;
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.iter().flatten().sum()
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        let correct: usize = (0..self.classes.len())
+            .map(|idx| self.counts[idx][idx])
+            .sum();
+        if self.total() == 0 {
+            0.0
+        } else {
+            correct as f64 / self.total() as f64
+        }
+    }
+
+    // binary matrix of one class against all the others
+    pub fn one_vs_rest(&self, class_idx: usize) -> ConfusionMatrix {
+        let mut matrix = ConfusionMatrix::new();
+        for (actual, counts) in self.counts.iter().enumerate() {
+            for (predicted, count) in counts.iter().enumerate() {
+                match (actual == class_idx, predicted == class_idx) {
+                    (true, true) => matrix.true_pos += count,
+                    (true, false) => matrix.false_neg += count,
+                    (false, true) => matrix.false_pos += count,
+                    (false, false) => matrix.true_neg += count,
+                }
+            }
+        }
+        matrix
+    }
+
+    // unweighted mean of the F1 of every class
+    pub fn macro_f1(&self) -> f64 {
+        if self.classes.is_empty() {
+            return 0.0;
+        }
+        (0..self.classes.len())
+            .map(|class_idx| self.one_vs_rest(class_idx).f1())
+            .sum::<f64>()
+            / self.classes.len() as f64
+    }
+}
@@ -0,0 +1,151 @@
+// tests
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::models::{
+        classifier::cnf_model::CnfModel,
+        clause::{DisjunctiveClause, Selector},
+        data_handling::{
+            attribute_values_set::{AttributeValuesSet, AttributeValuesSetList},
+            row::Row,
+        },
+        evaluation::{
+            confusion_matrix::ConfusionMatrix,
+            cross_validation::{CrossValidationReport, FoldResult},
+            multi_class_confusion_matrix::MultiClassConfusionMatrix,
+        },
+    };
+
+    fn row(color: &str) -> Row {
+        Row {
+            class: "".to_string(),
+            attributes: AttributeValuesSetList::from_vec(vec![AttributeValuesSet::Cat(
+                "color".to_string(),
+                HashSet::from([color.to_string()]),
+            )]),
+        }
+    }
+
+    #[test]
+    fn test_metrics() {
+        let matrix = ConfusionMatrix {
+            true_pos: 6,
+            false_pos: 2,
+            true_neg: 8,
+            false_neg: 4,
+        };
+
+        assert_eq!(matrix.total(), 20);
+        assert!((matrix.accuracy() - 0.7).abs() < 1e-9);
+        assert!((matrix.precision() - 0.75).abs() < 1e-9);
+        assert!((matrix.recall() - 0.6).abs() < 1e-9);
+        assert!((matrix.specificity() - 0.8).abs() < 1e-9);
+        assert!((matrix.f1() - 2.0 * 0.75 * 0.6 / 1.35).abs() < 1e-9);
+        assert!((matrix.balanced_accuracy() - 0.7).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_metrics_empty_denominators() {
+        let matrix = ConfusionMatrix::new();
+
+        assert_eq!(matrix.accuracy(), 0.0);
+        assert_eq!(matrix.precision(), 0.0);
+        assert_eq!(matrix.recall(), 0.0);
+        assert_eq!(matrix.f1(), 0.0);
+        assert_eq!(matrix.balanced_accuracy(), 0.0);
+    }
+
+    #[test]
+    fn test_from_predictions() {
+        let model = CnfModel::new(vec![DisjunctiveClause::new(vec![Selector::new_eq(
+            "color".to_string(),
+            "blue".to_string(),
+        )])]);
+
+        let positives = vec![row("blue"), row("blue"), row("red")];
+        let negatives = vec![row("green"), row("blue")];
+        let matrix =
+            ConfusionMatrix::from_predictions(|r| model.predict(r), &positives, &negatives);
+
+        assert_eq!(
+            matrix,
+            ConfusionMatrix {
+                true_pos: 2,
+                false_pos: 1,
+                true_neg: 1,
+                false_neg: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_cross_validation_mean_std() {
+        let fold = |true_pos, false_neg, n_clauses| FoldResult {
+            evaluation: ConfusionMatrix {
+                true_pos,
+                false_pos: 0,
+                true_neg: 2,
+                false_neg,
+            },
+            n_clauses,
+        };
+        let report = CrossValidationReport {
+            folds: vec![fold(2, 0, 3), fold(1, 1, 5), fold(0, 2, 7)],
+        };
+
+        let (mean, std) = report.mean_std(|fold| fold.n_clauses as f64);
+        assert!((mean - 5.0).abs() < 1e-9);
+        assert!((std - 2.0).abs() < 1e-9);
+
+        let (mean, std) = report.mean_std(|fold| fold.evaluation.recall());
+        assert!((mean - 0.5).abs() < 1e-9);
+        assert!((std - 0.5).abs() < 1e-9);
+
+        let (mean, std) = CrossValidationReport::default().mean_std(|fold| fold.n_clauses as f64);
+        assert_eq!((mean, std), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_multi_class_confusion_matrix() {
+        let classes = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let mut matrix = MultiClassConfusionMatrix::new(classes);
+        for (actual, predicted, count) in [
+            ("a", "a", 5),
+            ("a", "b", 1),
+            ("b", "b", 3),
+            ("b", "c", 1),
+            ("c", "a", 2),
+            ("c", "c", 4),
+        ] {
+            for _ in 0..count {
+                matrix.add(actual, predicted);
+            }
+        }
+        // rows of a class the matrix does not know are skipped
+        matrix.add("d", "a");
+
+        assert_eq!(
+            matrix.counts,
+            vec![vec![5, 1, 0], vec![0, 3, 1], vec![2, 0, 4]]
+        );
+        assert_eq!(matrix.total(), 16);
+        assert!((matrix.accuracy() - 12.0 / 16.0).abs() < 1e-9);
+        assert_eq!(
+            matrix.one_vs_rest(0),
+            ConfusionMatrix {
+                true_pos: 5,
+                false_pos: 2,
+                true_neg: 8,
+                false_neg: 1,
+            }
+        );
+        let f1s = (0..3)
+            .map(|idx| matrix.one_vs_rest(idx).f1())
+            .collect::<Vec<f64>>();
+        assert!((matrix.macro_f1() - f1s.iter().sum::<f64>() / 3.0).abs() < 1e-9);
+
+        assert_eq!(MultiClassConfusionMatrix::default().accuracy(), 0.0);
+        assert_eq!(MultiClassConfusionMatrix::default().macro_f1(), 0.0);
+    }
+}
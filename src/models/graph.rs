@@ -1,4 +1,5 @@
 pub mod edge;
+pub mod incremental_clique;
 pub mod rejectability;
 pub mod rejectability_graph;
 pub mod unit_tests;
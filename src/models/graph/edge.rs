@@ -1,8 +1,9 @@
-use crate::models::data_handling::attribute_values_set::AttributeValuesSetList;
+use crate::models::data_handling::encoded_values_set::EncodedValuesSetList;
 
 // pub enum Edge {
 //     No(),
 //     E(usize, usize, AttributeValuesSetList),
 // }
 
-pub type Edge = Option<AttributeValuesSetList>;
+// the clause of an edge is stored encoded with the dictionary of its graph
+pub type Edge = Option<EncodedValuesSetList>;
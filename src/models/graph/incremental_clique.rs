@@ -0,0 +1,92 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::models::data_handling::encoded_values_set::EncodedValuesSetList;
+
+use super::rejectability_graph::Graph;
+
+// clique grown one vertex at a time: its clause is only intersected with the
+// edges of the new vertex, and every positive keeps the number of its values
+// still in the clause, so checking that the clique stays complete is cheap.
+// positives already missed by the covered cliques do not count again
+#[derive(Clone, Debug)]
+pub struct IncrementalClique {
+    pub clique: HashSet<usize>,
+    pub clause: EncodedValuesSetList,
+    coverage: Vec<usize>,
+    uncovered_positives: usize,
+}
+
+impl IncrementalClique {
+    pub fn new(graph: &Graph, vertex: usize) -> IncrementalClique {
+        let clause = graph
+            .reject_one_negative
+            .get(vertex)
+            .cloned()
+            .unwrap_or_default();
+        let coverage = graph
+            .encoded_positives
+            .iter()
+            .map(|positive| clause.intersection(positive).n_values())
+            .collect::<Vec<usize>>();
+        let uncovered_positives = coverage
+            .iter()
+            .enumerate()
+            .filter(|(positive, count)| **count == 0 && !graph.missed_positives.contains(positive))
+            .count();
+
+        IncrementalClique {
+            clique: HashSet::from([vertex]),
+            clause,
+            coverage,
+            uncovered_positives,
+        }
+    }
+
+    // clause of the clique with a neighbor of every clique vertex added, only the
+    // edges of the new vertex are intersected
+    pub fn clause_with(&self, graph: &Graph, vertex: usize) -> EncodedValuesSetList {
+        let mut new_clause = self.clause.clone();
+        for u in &self.clique {
+            let edge_clause = graph.adj_mtx[*u][vertex].as_ref().unwrap();
+            new_clause = new_clause.intersection(edge_clause);
+        }
+        new_clause
+    }
+
+    // add a neighbor of every clique vertex if the clique stays complete,
+    // returns false and leaves the clique unchanged otherwise
+    pub fn try_add(&mut self, graph: &Graph, vertex: usize) -> bool {
+        let new_clause = self.clause_with(graph, vertex);
+
+        // values lost by the clause, and the positives having them
+        let removed = self.clause.difference(&new_clause);
+        let mut lost: HashMap<usize, usize> = HashMap::new();
+        for (attr_idx, values) in removed.sets.iter().enumerate() {
+            for id in values.iter() {
+                for positive in graph.positives_with_value(attr_idx, id) {
+                    *lost.entry(*positive).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let newly_uncovered = lost
+            .iter()
+            .filter(|(positive, n_lost)| {
+                self.coverage[**positive] > 0
+                    && self.coverage[**positive] == **n_lost
+                    && !graph.missed_positives.contains(positive)
+            })
+            .count();
+        if self.uncovered_positives + newly_uncovered > graph.remaining_missed_positives() {
+            return false;
+        }
+
+        for (positive, n_lost) in lost {
+            self.coverage[positive] -= n_lost;
+        }
+        self.uncovered_positives += newly_uncovered;
+        self.clause = new_clause;
+        self.clique.insert(vertex);
+        true
+    }
+}
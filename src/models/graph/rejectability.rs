@@ -1,18 +1,19 @@
-use ordered_float::OrderedFloat;
-use rand::rngs::StdRng;
-use std::collections::HashSet;
+use std::thread;
 
 use super::{
     super::data_handling::{
-        attribute_values_set::{AttributeValuesSet, AttributeValuesSetList},
-        dataset::Dataset,
-        row::Row,
+        attribute_values_set::AttributeValuesSetList, dataset::Dataset,
+        encoded_values_set::EncodedValuesSetList, row::Row,
     },
-    rejectability_graph::Graph,
+    rejectability_graph::{Graph, NoiseTolerance},
 };
 
 // create rejectability graph
-pub fn create_rejectability_graph(rng: StdRng, dataset: &Dataset) -> Graph {
+pub fn create_rejectability_graph(
+    dataset: &Dataset,
+    threads: usize,
+    tolerance: NoiseTolerance,
+) -> Graph {
     // create a complete clause (accepts all posotive)
     let accept_all_positive = construct_attribute_sets(
         &dataset.learning_pos,
@@ -29,128 +30,109 @@ pub fn create_rejectability_graph(rng: StdRng, dataset: &Dataset) -> Graph {
     println!("complete clause {}", accept_all_positive);
 
     let mut graph = Graph::new(
-        rng,
         dataset.learning_neg.len(),
         reject_only_one_negative,
         dataset.learning_pos.clone(),
+        dataset.learning_neg.clone(),
     );
+    graph.max_missed_positives =
+        NoiseTolerance::allowed(tolerance.positives, dataset.learning_pos.len());
+    graph.max_unrejected_negatives =
+        NoiseTolerance::allowed(tolerance.negatives, dataset.learning_neg.len());
 
     // add an edge for every possible pair of negative examples
-    for i in 0..dataset.learning_neg.len() {
-        for j in i + 1..dataset.learning_neg.len() {
-            // get a list of sets with every selector of the two negative examples
-            let negative_pair_attrs = construct_attribute_sets(&dataset.learning_neg, &[i, j]);
-
-            // clause is a list of sets containing every selector
-            // that is present in every positive element and
-            // not in the two negative elements
-            let mut clause: AttributeValuesSetList = AttributeValuesSetList {
-                list: c![AttributeValuesSet::Empty, for _i in 0..dataset.learning_pos[0].attributes.len()],
-            };
-            let mut exists_clause_for_all_positive = true;
-
-            // check every element in the positive dataset to see if theres a complete clause tha rejects the pair
-            for (positive_idx, positive) in dataset.learning_pos.iter().enumerate() {
-                let exists_clause_two_neg_on_pos =
-                    exists_clause_one_positive(positive, &negative_pair_attrs);
-
-                if !exists_clause_two_neg_on_pos {
-                    exists_clause_for_all_positive = false;
-                    break;
-                }
-
-                // find the clause that rejects the pair and accepts current positive element
-                let singular_clause_two_neg_one_pos = find_clause_one_positive(
-                    &dataset.learning_pos,
-                    positive_idx,
-                    &negative_pair_attrs,
-                );
-
-                // add to the clause, the new selectors for this positive element
-                clause = clause.union(&singular_clause_two_neg_one_pos);
-                // clause = update_clause(&clause, &singular_clause_two_neg_one_pos);
-
-                exists_clause_for_all_positive =
-                    exists_clause_for_all_positive && exists_clause_two_neg_on_pos;
-            }
-
-            if exists_clause_for_all_positive {
-                graph.add_edge(i, j, &clause);
-                println!(
-                    "There's an edge between {} and {}, with clause {}",
-                    i, j, clause
-                );
-            }
-        }
+    for (i, j, clause) in find_rejectability_edges(
+        &graph.encoded_positives,
+        &graph.encoded_negatives,
+        graph.max_missed_positives,
+        threads,
+    ) {
+        println!(
+            "There's an edge between {} and {}, with clause {}",
+            i,
+            j,
+            graph.dictionary.decode(&clause)
+        );
+        graph.add_encoded_edge(i, j, clause);
     }
 
     graph
 }
 
-pub fn exists_clause_one_positive(
-    positive: &Row,
-    negative_pair_attrs: &AttributeValuesSetList,
-) -> bool {
-    let mut exists_clause = false;
-
-    for (pos_attr_idx, pos_attr_set) in positive.attributes.list.iter().enumerate() {
-        match &negative_pair_attrs.list[pos_attr_idx] {
-            AttributeValuesSet::Num(_, neg_values_set) => {
-                if let AttributeValuesSet::Num(_, pos_value_set) = pos_attr_set {
-                    let first_val_pos = pos_value_set.iter().next().unwrap();
-                    exists_clause = exists_clause || !neg_values_set.contains(&first_val_pos);
-                }
-            }
-            AttributeValuesSet::Cat(_, neg_values_set) => {
-                if let AttributeValuesSet::Cat(_, pos_value_set) = pos_attr_set {
-                    let first_val_pos = pos_value_set.iter().next().unwrap();
-                    exists_clause = exists_clause || !neg_values_set.contains(first_val_pos);
+// every pair (i, j), i < j, of negatives that can be rejected together, with its clause,
+// sorted by pair so the result does not depend on the number of threads
+pub fn find_rejectability_edges(
+    positives: &[EncodedValuesSetList],
+    negatives: &[EncodedValuesSetList],
+    max_missed_positives: usize,
+    threads: usize,
+) -> Vec<(usize, usize, EncodedValuesSetList)> {
+    let n_neg = negatives.len();
+    let threads = threads.clamp(1, n_neg.max(1));
+
+    // row i has n_neg - i - 1 pairs, interleaving the rows balances the threads
+    let edges_of_rows = |first_row: usize| {
+        let mut edges = vec![];
+        for i in (first_row..n_neg).step_by(threads) {
+            for j in i + 1..n_neg {
+                if let Some(clause) = find_pair_clause(
+                    positives,
+                    &negatives[i],
+                    &negatives[j],
+                    max_missed_positives,
+                ) {
+                    edges.push((i, j, clause));
                 }
             }
-            AttributeValuesSet::Empty => continue,
         }
-    }
-
-    exists_clause
-}
+        edges
+    };
 
-pub fn find_clause_one_positive(
-    positive_dataset: &[Row],
-    positive_idx: usize,
-    negative_pair_attrs: &AttributeValuesSetList,
-) -> AttributeValuesSetList {
-    let positive_element_attrs = construct_attribute_sets(positive_dataset, &[positive_idx]);
-    let mut clause: AttributeValuesSetList = AttributeValuesSetList {
-        list: c![AttributeValuesSet::Empty, for _i in 0..positive_dataset[0].attributes.len()],
+    let mut edges = if threads == 1 {
+        edges_of_rows(0)
+    } else {
+        thread::scope(|scope| {
+            let handles = (0..threads)
+                .map(|first_row| scope.spawn(move || edges_of_rows(first_row)))
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        })
     };
+    edges.sort_unstable_by_key(|(i, j, _)| (*i, *j));
 
-    for (pos_attr_idx, pos_attr) in positive_element_attrs.list.iter().enumerate() {
-        match pos_attr {
-            AttributeValuesSet::Num(_, values_set) => {
-                if let AttributeValuesSet::Num(attr_name, neg_values_set) =
-                    &negative_pair_attrs.list[pos_attr_idx]
-                {
-                    clause.list[pos_attr_idx] = AttributeValuesSet::Num(
-                        attr_name.clone(),
-                        values_set.difference(neg_values_set).cloned().collect(),
-                    );
-                }
-            }
-            AttributeValuesSet::Cat(_, values_set) => {
-                if let AttributeValuesSet::Cat(attr_name, neg_values_set) =
-                    &negative_pair_attrs.list[pos_attr_idx]
-                {
-                    clause.list[pos_attr_idx] = AttributeValuesSet::Cat(
-                        attr_name.clone(),
-                        values_set.difference(neg_values_set).cloned().collect(),
-                    );
-                }
+    edges
+}
+
+// clause that rejects the two negatives and accepts every positive but at most
+// max_missed_positives of them, if there is one
+pub fn find_pair_clause(
+    positives: &[EncodedValuesSetList],
+    negative_1: &EncodedValuesSetList,
+    negative_2: &EncodedValuesSetList,
+    max_missed_positives: usize,
+) -> Option<EncodedValuesSetList> {
+    // every selector of the two negative examples
+    let negative_pair_attrs = negative_1.union(negative_2);
+
+    // the clause keeps, for every positive element, its selectors
+    // that are not in the two negative elements
+    let mut clause = EncodedValuesSetList::default();
+    let mut missed_positives = 0;
+    for positive in positives {
+        let clause_two_neg_one_pos = positive.difference(&negative_pair_attrs);
+        if !clause_two_neg_one_pos.has_values() {
+            missed_positives += 1;
+            if missed_positives > max_missed_positives {
+                return None;
             }
-            AttributeValuesSet::Empty => continue,
         }
+        clause = clause.union(&clause_two_neg_one_pos);
     }
 
-    clause
+    Some(clause)
 }
 
 // construct a list of sets containig the values of every atrribute for each element in a subest of the dataset
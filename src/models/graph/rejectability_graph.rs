@@ -2,36 +2,101 @@ use std::collections::{HashMap, HashSet};
 
 use rand::{rngs::StdRng, Rng};
 
-use crate::models::data_handling::{attribute_values_set::AttributeValuesSetList, row::Row};
+use crate::models::{
+    clause::DisjunctiveClause,
+    data_handling::{
+        attribute_values_set::AttributeValuesSetList, encoded_values_set::EncodedValuesSetList,
+        row::Row, value_dictionary::ValueDictionary,
+    },
+};
+
+use super::{edge::Edge, rejectability::construct_attribute_sets};
+
+// fractions of the learning examples the model may misclassify: its clauses
+// together may miss up to `positives` of the positives, and the cover may leave
+// up to `negatives` of the negatives unrejected. a positive equal to a negative
+// is missed by any clause rejecting the negative, even beyond the tolerance
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NoiseTolerance {
+    pub positives: f64,
+    pub negatives: f64,
+}
 
-use super::edge::Edge;
+impl NoiseTolerance {
+    // number of examples out of n the fraction allows
+    pub fn allowed(fraction: f64, n: usize) -> usize {
+        (fraction * n as f64 + 1e-9).floor() as usize
+    }
+}
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Graph {
     pub adj_mtx: Vec<Vec<Edge>>,
     pub edge_dict: HashMap<usize, HashSet<usize>>,
     pub n_vertex: usize,
     pub available_vertex: HashSet<usize>,
-    pub reject_one_negative: Vec<AttributeValuesSetList>,
+    pub reject_one_negative: Vec<EncodedValuesSetList>,
     pub positive_dataset: Vec<Row>,
-    rng: StdRng,
+    pub negative_dataset: Vec<Row>,
+    // value ids of the examples, clauses of the graph are bitsets over them
+    pub dictionary: ValueDictionary,
+    pub encoded_positives: Vec<EncodedValuesSetList>,
+    pub encoded_negatives: Vec<EncodedValuesSetList>,
+    // positives having each value id of each attribute
+    positive_value_index: Vec<Vec<Vec<usize>>>,
+    // positives the clauses of the cover may reject, negatives the cover may leave
+    pub max_missed_positives: usize,
+    pub max_unrejected_negatives: usize,
+    // positives rejected by the clauses of the covered cliques
+    pub missed_positives: HashSet<usize>,
 }
 
 impl Graph {
     pub fn new(
-        rng: StdRng,
         num_vertex: usize,
         reject_one_negative: Vec<AttributeValuesSetList>,
         positive_dataset: Vec<Row>,
+        negative_dataset: Vec<Row>,
     ) -> Graph {
+        let dictionary =
+            ValueDictionary::from_rows(positive_dataset.iter().chain(&negative_dataset));
+        let encode_rows = |rows: &[Row]| {
+            rows.iter()
+                .map(|row| dictionary.encode(&row.attributes))
+                .collect::<Vec<EncodedValuesSetList>>()
+        };
+        let encoded_positives = encode_rows(&positive_dataset);
+        let mut positive_value_index: Vec<Vec<Vec<usize>>> = dictionary
+            .attributes
+            .iter()
+            .map(|attribute| vec![vec![]; attribute.values.len()])
+            .collect();
+        for (positive_idx, positive) in encoded_positives.iter().enumerate() {
+            for (attr_idx, values) in positive.sets.iter().enumerate() {
+                for id in values.iter() {
+                    positive_value_index[attr_idx][id].push(positive_idx);
+                }
+            }
+        }
+
         let mut graph = Graph {
             adj_mtx: vec![],
             edge_dict: HashMap::new(),
             n_vertex: 0,
             available_vertex: HashSet::new(),
-            reject_one_negative,
+            reject_one_negative: reject_one_negative
+                .iter()
+                .map(|clause| dictionary.encode(clause))
+                .collect(),
+            encoded_positives,
+            positive_value_index,
+            max_missed_positives: 0,
+            max_unrejected_negatives: 0,
+            missed_positives: HashSet::new(),
+            encoded_negatives: encode_rows(&negative_dataset),
             positive_dataset,
-            rng,
+            negative_dataset,
+            dictionary,
         };
 
         graph.n_vertex = num_vertex;
@@ -46,8 +111,13 @@ impl Graph {
     }
 
     pub fn add_edge(&mut self, u: usize, v: usize, clause_values: &AttributeValuesSetList) {
+        let encoded_clause = self.dictionary.encode(clause_values);
+        self.add_encoded_edge(u, v, encoded_clause);
+    }
+
+    pub fn add_encoded_edge(&mut self, u: usize, v: usize, clause_values: EncodedValuesSetList) {
         self.adj_mtx[u][v] = Some(clause_values.clone());
-        self.adj_mtx[v][u] = Some(clause_values.clone());
+        self.adj_mtx[v][u] = Some(clause_values);
 
         self.edge_dict.get_mut(&u).unwrap().insert(v);
         self.edge_dict.get_mut(&v).unwrap().insert(u);
@@ -61,15 +131,22 @@ impl Graph {
         self.adj_mtx[vertex_1][vertex_2].is_some()
     }
 
-    pub fn select_random_vertex(&mut self) -> usize {
+    pub fn select_random_vertex(&self, rng: &mut StdRng) -> usize {
         loop {
-            let selected = self.rng.gen_range(0..self.n_vertex);
+            let selected = rng.gen_range(0..self.n_vertex);
             if self.available_vertex.contains(&selected) {
                 return selected;
             }
         }
     }
 
+    pub fn positives_with_value(&self, attr_idx: usize, id: usize) -> &[usize] {
+        self.positive_value_index
+            .get(attr_idx)
+            .and_then(|ids| ids.get(id))
+            .map_or(&[], |positives| positives.as_slice())
+    }
+
     pub fn get_neighbor_candidates(&self, vertex: usize) -> HashSet<usize> {
         let all_neighbors = self.edge_dict.get(&vertex).unwrap();
         let available_neighbors = all_neighbors.intersection(&self.available_vertex);
@@ -85,7 +162,13 @@ impl Graph {
     }
 
     pub fn get_clique_clause(&self, clique: HashSet<usize>) -> AttributeValuesSetList {
-        let mut clique_clause = AttributeValuesSetList::new();
+        self.dictionary
+            .decode(&self.get_encoded_clique_clause(&clique))
+    }
+
+    // clause of the clique: intersection of the clauses of its edges
+    pub fn get_encoded_clique_clause(&self, clique: &HashSet<usize>) -> EncodedValuesSetList {
+        let mut clique_clause = EncodedValuesSetList::default();
 
         if clique.len() == 1 {
             let vertex = clique.iter().next().unwrap();
@@ -97,7 +180,7 @@ impl Graph {
             for j in i + 1..vertex_list.len() {
                 let (v1, v2): (usize, usize) = (vertex_list[i], vertex_list[j]);
                 let edge_clause = self.adj_mtx[v1][v2].as_ref().unwrap();
-                clique_clause = if clique_clause.list.is_empty() {
+                clique_clause = if clique_clause.is_empty() {
                     edge_clause.clone()
                 } else {
                     clique_clause.intersection(edge_clause)
@@ -107,4 +190,63 @@ impl Graph {
 
         clique_clause
     }
+
+    // build the disjunctive clause of a clique, numeric values are turned
+    // into thresholds that separate them from the negatives of the clique
+    pub fn get_clique_disjunctive_clause(&self, clique: HashSet<usize>) -> DisjunctiveClause {
+        let clique_negatives = clique.iter().copied().collect::<Vec<usize>>();
+        let rejected_values = construct_attribute_sets(&self.negative_dataset, &clique_negatives);
+        let clique_clause = self.get_clique_clause(clique);
+
+        DisjunctiveClause::from_clause_and_rejected(&clique_clause, &rejected_values)
+    }
+
+    // positives the clause rejects
+    fn positives_missed_by(&self, clause: &EncodedValuesSetList) -> HashSet<usize> {
+        self.encoded_positives
+            .iter()
+            .enumerate()
+            .filter(|(_, positive)| !clause.intersection(positive).has_values())
+            .map(|(positive_idx, _)| positive_idx)
+            .collect()
+    }
+
+    // positives a new clause may still reject besides the already missed ones
+    pub fn remaining_missed_positives(&self) -> usize {
+        self.max_missed_positives
+            .saturating_sub(self.missed_positives.len())
+    }
+
+    // a clique is complete when its clause still accepts every positive element,
+    // but the remaining_missed_positives ones not missed yet. the solvers check it
+    // incrementally with IncrementalClique::try_add, this full rebuild is the
+    // reference of the tests
+    #[cfg(test)]
+    pub fn clique_is_complete(&self, clique: &HashSet<usize>) -> bool {
+        let clique_clause = self.get_encoded_clique_clause(clique);
+
+        let newly_missed = self
+            .positives_missed_by(&clique_clause)
+            .difference(&self.missed_positives)
+            .count();
+        newly_missed <= self.remaining_missed_positives()
+    }
+
+    // the cover stops once at most max_unrejected_negatives vertices are left
+    pub fn is_covered(&self) -> bool {
+        self.available_vertex.len() <= self.max_unrejected_negatives
+    }
+
+    // remove the clique from the available vertices and return its clause,
+    // the positives it rejects count against the tolerance of the next cliques
+    pub fn cover_clique(&mut self, clique: HashSet<usize>) -> DisjunctiveClause {
+        self.remove_vertex_set_from_available(&clique);
+        let missed = self.positives_missed_by(&self.get_encoded_clique_clause(&clique));
+        self.missed_positives.extend(missed);
+        println!("Best clique: |{}| {:?}", clique.len(), clique);
+
+        let clique_clause = self.get_clique_disjunctive_clause(clique);
+        println!("Clique clause: {}", &clique_clause);
+        clique_clause
+    }
 }
@@ -9,20 +9,20 @@ mod tests {
             dataset::Dataset,
         },
         graph::{
+            incremental_clique::IncrementalClique,
             rejectability::{
-                construct_attribute_sets, create_rejectability_graph, exists_clause_one_positive,
-                find_clause_one_positive,
+                construct_attribute_sets, create_rejectability_graph, find_pair_clause,
             },
-            rejectability_graph::Graph,
+            rejectability_graph::{Graph, NoiseTolerance},
         },
     };
 
-    use rand::{rngs::StdRng, SeedableRng};
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_graph_new() {
-        let rng = StdRng::seed_from_u64(1000);
-        let mut graph = Graph::new(rng, 5, vec![], vec![]);
+        let mut graph = Graph::new(5, vec![], vec![], vec![]);
 
         let clause_values = AttributeValuesSetList {
             list: vec![
@@ -58,24 +58,23 @@ mod tests {
 
     #[test]
     fn test_graph_select_random_vertex() {
-        let rng = StdRng::seed_from_u64(1000);
-        let mut graph = Graph::new(rng, 5, vec![], vec![]);
+        let mut rng = StdRng::seed_from_u64(1000);
+        let graph = Graph::new(5, vec![], vec![], vec![]);
 
-        let mut random_vertex = graph.select_random_vertex();
+        let mut random_vertex = graph.select_random_vertex(&mut rng);
         let options = HashSet::from([0, 1, 2, 3, 4]);
         assert!(options.contains(&random_vertex));
 
-        random_vertex = graph.select_random_vertex();
+        random_vertex = graph.select_random_vertex(&mut rng);
         assert!(options.contains(&random_vertex));
 
-        random_vertex = graph.select_random_vertex();
+        random_vertex = graph.select_random_vertex(&mut rng);
         assert!(options.contains(&random_vertex));
     }
 
     #[test]
     fn test_graph_get_neighbor_candidates() {
-        let rng = StdRng::seed_from_u64(1000);
-        let mut graph = Graph::new(rng, 5, vec![], vec![]);
+        let mut graph = Graph::new(5, vec![], vec![], vec![]);
 
         let clause_values = AttributeValuesSetList {
             list: vec![
@@ -117,18 +116,35 @@ mod tests {
     #[test]
     fn test_create_rejectability_graph() {
         let rng = StdRng::seed_from_u64(1000);
-        let dataset = Dataset::new(rng, "datasets/test1.csv", "class", "yes", 80);
+        let dataset = Dataset::new(rng, "datasets/test1.csv", "class", "yes", 80).unwrap();
 
-        let rng = StdRng::seed_from_u64(1000);
-        let graph = create_rejectability_graph(rng, &dataset);
+        let graph = create_rejectability_graph(&dataset, 1, NoiseTolerance::default());
 
         assert_eq!(graph.n_vertex, 4);
     }
 
+    #[test]
+    fn test_parallel_rejectability_graph() {
+        let rng = StdRng::seed_from_u64(1000);
+        let dataset =
+            Dataset::new(rng.clone(), "datasets/test2.csv", "class", "perfect", 80).unwrap();
+
+        let sequential = create_rejectability_graph(&dataset, 1, NoiseTolerance::default());
+        assert!(sequential
+            .edge_dict
+            .values()
+            .any(|neighbors| !neighbors.is_empty()));
+        for threads in [2, 3, 64] {
+            let parallel = create_rejectability_graph(&dataset, threads, NoiseTolerance::default());
+            assert_eq!(parallel.adj_mtx, sequential.adj_mtx);
+            assert_eq!(parallel.edge_dict, sequential.edge_dict);
+        }
+    }
+
     #[test]
     fn test_construct_attribute_sets() {
         let rng = StdRng::seed_from_u64(1000);
-        let dataset = Dataset::new(rng, "datasets/test1.csv", "class", "yes", 80);
+        let dataset = Dataset::new(rng, "datasets/test1.csv", "class", "yes", 80).unwrap();
 
         assert_eq!(dataset.learning_neg.len(), 4);
         let subset = [0, 1, 2];
@@ -155,33 +171,35 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_exists_clause_one_positive() {
         let rng = StdRng::seed_from_u64(1000);
-        let dataset = Dataset::new(rng, "datasets/test1.csv", "class", "yes", 80);
+        let dataset = Dataset::new(rng, "datasets/test1.csv", "class", "yes", 80).unwrap();
+        let graph = create_rejectability_graph(&dataset, 1, NoiseTolerance::default());
 
         let positive_idx = 0;
+        let positive = [graph.encoded_positives[positive_idx].clone()];
+        let negatives = &graph.encoded_negatives;
 
-        let negative_pair_attrs = construct_attribute_sets(&dataset.learning_neg, &[0, 1]);
-        let exists_clause =
-            exists_clause_one_positive(&dataset.learning_pos[positive_idx], &negative_pair_attrs);
+        let exists_clause = find_pair_clause(&positive, &negatives[0], &negatives[1], 0).is_some();
         assert_eq!(exists_clause, true);
 
-        let negative_pair_attrs = construct_attribute_sets(&dataset.learning_neg, &[1, 2]);
-        let exists_clause =
-            exists_clause_one_positive(&dataset.learning_pos[positive_idx], &negative_pair_attrs);
+        let exists_clause = find_pair_clause(&positive, &negatives[1], &negatives[2], 0).is_some();
         assert_eq!(exists_clause, false);
     }
 
     #[test]
     fn test_find_clause_one_positive() {
         let rng = StdRng::seed_from_u64(1000);
-        let dataset = Dataset::new(rng, "datasets/test1.csv", "class", "yes", 80);
+        let dataset = Dataset::new(rng, "datasets/test1.csv", "class", "yes", 80).unwrap();
+        let graph = create_rejectability_graph(&dataset, 1, NoiseTolerance::default());
 
         let positive_idx = 0;
+        let positive = [graph.encoded_positives[positive_idx].clone()];
+        let negatives = &graph.encoded_negatives;
 
-        let negative_pair_attrs = construct_attribute_sets(&dataset.learning_neg, &[0, 1]);
-        let clause =
-            find_clause_one_positive(&dataset.learning_pos, positive_idx, &negative_pair_attrs);
+        let clause = find_pair_clause(&positive, &negatives[0], &negatives[1], 0).unwrap();
+        let clause = graph.dictionary.decode(&clause);
         assert_eq!(clause.list.len(), 2);
         assert_eq!(
             clause.list[0],
@@ -192,17 +210,137 @@ mod tests {
             AttributeValuesSet::Cat("color".to_string(), HashSet::from(["red".to_string()]))
         );
 
-        let negative_pair_attrs = construct_attribute_sets(&dataset.learning_neg, &[1, 2]);
-        let clause =
-            find_clause_one_positive(&dataset.learning_pos, positive_idx, &negative_pair_attrs);
-        assert_eq!(clause.list.len(), 2);
+        // the positive is missed, which one tolerated positive allows
+        let clause = find_pair_clause(&positive, &negatives[1], &negatives[2], 1).unwrap();
+        assert!(!clause.has_values());
+    }
+
+    #[test]
+    fn test_incremental_clique() {
+        let mut rng = StdRng::seed_from_u64(1000);
+        let dataset =
+            Dataset::new(rng.clone(), "datasets/hayes_roth.csv", "class", "1", 80).unwrap();
+        let graph = create_rejectability_graph(&dataset, 1, NoiseTolerance::default());
+
+        for start in 0..graph.n_vertex {
+            let mut clique = IncrementalClique::new(&graph, start);
+            assert_eq!(
+                clique.clause,
+                graph.get_encoded_clique_clause(&clique.clique)
+            );
+
+            let mut candidates = graph
+                .get_neighbor_candidates(start)
+                .into_iter()
+                .collect::<Vec<usize>>();
+            candidates.sort_unstable();
+            candidates.shuffle(&mut rng);
+            for candidate in candidates {
+                if !clique.clique.iter().all(|v| graph.is_edge(*v, candidate)) {
+                    continue;
+                }
+                let mut new_clique = clique.clique.clone();
+                new_clique.insert(candidate);
+                let complete = graph.clique_is_complete(&new_clique);
+
+                // the clique only changes when the bigger clique is complete
+                assert_eq!(clique.try_add(&graph, candidate), complete);
+                assert_eq!(clique.clique.contains(&candidate), complete);
+                assert_eq!(
+                    clique.clause,
+                    graph.get_encoded_clique_clause(&clique.clique)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_incremental_clique_with_tolerance() {
+        let mut rng = StdRng::seed_from_u64(1000);
+        let dataset =
+            Dataset::new(rng.clone(), "datasets/hayes_roth.csv", "class", "1", 80).unwrap();
+        let tolerance = NoiseTolerance {
+            positives: 0.05,
+            negatives: 0.0,
+        };
+        let graph = create_rejectability_graph(&dataset, 1, tolerance);
+        assert!(graph.max_missed_positives > 0);
+
+        // cliques the strict graph refuses are grown while few enough positives are missed
+        let mut grown_with_missed_positives = false;
+        for start in 0..graph.n_vertex {
+            let mut clique = IncrementalClique::new(&graph, start);
+            let mut candidates = graph
+                .get_neighbor_candidates(start)
+                .into_iter()
+                .collect::<Vec<usize>>();
+            candidates.sort_unstable();
+            candidates.shuffle(&mut rng);
+            for candidate in candidates {
+                if !clique.clique.iter().all(|v| graph.is_edge(*v, candidate)) {
+                    continue;
+                }
+                let mut new_clique = clique.clique.clone();
+                new_clique.insert(candidate);
+                let complete = graph.clique_is_complete(&new_clique);
+
+                assert_eq!(clique.try_add(&graph, candidate), complete);
+                assert_eq!(
+                    clique.clause,
+                    graph.get_encoded_clique_clause(&clique.clique)
+                );
+                let clause = graph.get_encoded_clique_clause(&new_clique);
+                grown_with_missed_positives |= complete
+                    && graph
+                        .encoded_positives
+                        .iter()
+                        .any(|positive| !clause.intersection(positive).has_values());
+            }
+        }
+        assert!(grown_with_missed_positives);
+    }
+
+    #[test]
+    fn test_noise_tolerance() {
+        assert_eq!(NoiseTolerance::allowed(0.0, 50), 0);
+        assert_eq!(NoiseTolerance::allowed(0.1, 50), 5);
+        assert_eq!(NoiseTolerance::allowed(0.05, 30), 1);
+        assert_eq!(NoiseTolerance::allowed(1.0, 7), 7);
+
+        let rng = StdRng::seed_from_u64(1000);
+        let dataset =
+            Dataset::new(rng.clone(), "datasets/hayes_roth.csv", "class", "1", 80).unwrap();
+        let strict = create_rejectability_graph(&dataset, 1, NoiseTolerance::default());
+        let tolerance = NoiseTolerance {
+            positives: 0.1,
+            negatives: 0.1,
+        };
+        let tolerant = create_rejectability_graph(&dataset, 1, tolerance);
+        assert_eq!(strict.max_missed_positives, 0);
+        assert_eq!(strict.max_unrejected_negatives, 0);
         assert_eq!(
-            clause.list[0],
-            AttributeValuesSet::Cat("size".to_string(), HashSet::new())
+            tolerant.max_missed_positives,
+            dataset.learning_pos.len() / 10
         );
         assert_eq!(
-            clause.list[1],
-            AttributeValuesSet::Cat("color".to_string(), HashSet::new())
+            tolerant.max_unrejected_negatives,
+            dataset.learning_neg.len() / 10
         );
+
+        // missing a few positives only adds edges
+        for u in 0..strict.n_vertex {
+            for v in 0..strict.n_vertex {
+                if strict.is_edge(u, v) {
+                    assert!(tolerant.is_edge(u, v));
+                }
+            }
+        }
+        let n_edges = |graph: &Graph| {
+            (0..graph.n_vertex)
+                .flat_map(|u| (0..graph.n_vertex).map(move |v| (u, v)))
+                .filter(|(u, v)| graph.is_edge(*u, *v))
+                .count()
+        };
+        assert!(n_edges(&tolerant) > n_edges(&strict));
     }
 }